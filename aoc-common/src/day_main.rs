@@ -0,0 +1,289 @@
+use crate::{debug, fetch, input, parse, visual, Solver};
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// A day specific option of a day binary, given as `--NAME`, followed by a
+/// value unless it is a switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flag {
+    name: &'static str,
+    takes_value: bool,
+}
+
+impl Flag {
+    pub const fn switch(name: &'static str) -> Self {
+        Flag {
+            name,
+            takes_value: false,
+        }
+    }
+
+    pub const fn value(name: &'static str) -> Self {
+        Flag {
+            name,
+            takes_value: true,
+        }
+    }
+}
+
+/// The day specific options given on the command line, by name without the
+/// leading `--`. Switches have no value.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Options(HashMap<&'static str, Option<String>>);
+
+impl Options {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn is_set(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.0.get(name)?.as_deref()
+    }
+
+    /// The value of option `name` parsed into a `T`, if it was given.
+    pub fn parse<T>(&self, name: &str) -> Result<Option<T>, AnyhowError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.value(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| anyhow!("Bad --{} value {}: {}", name, value, e))
+            })
+            .transpose()
+    }
+}
+
+/// A run of a day binary with day specific options, handed to its handler
+/// once the common options are applied.
+#[derive(Debug)]
+pub struct DayRun {
+    pub day: u8,
+    /// The parts asked for, both unless `--part` was given.
+    pub parts: Vec<u8>,
+    /// Path of the input, `-` for stdin.
+    pub path: String,
+    pub options: Options,
+}
+
+impl DayRun {
+    pub fn read_input(&self) -> Result<String, AnyhowError> {
+        input::read_input(&self.path)
+    }
+}
+
+/// Runs a day binary given day specific options, instead of solving the parts.
+pub type Handler = fn(&DayRun) -> Result<(), AnyhowError>;
+
+#[derive(Debug, PartialEq, Eq)]
+struct DayArgs {
+    parts: Vec<u8>,
+    input: Option<String>,
+    strict: bool,
+    debug: bool,
+    visualize: Option<PathBuf>,
+    options: Options,
+}
+
+/// Parses `[--part 1|2] [--strict true|false] [--debug] [--visualize DIR]
+/// [INPUT]`, along with the day specific `flags`: both parts are run when
+/// `--part` is absent, `-` as input means stdin. `--debug` prints the
+/// solvers' diagnostics on stderr. Without an input, `./input.txt` or else
+/// the cached input is read.
+fn parse_args(args: &[String], flags: &[Flag]) -> Result<DayArgs, AnyhowError> {
+    let mut parts = vec![1, 2];
+    let mut input = None;
+    let mut strict = true;
+    let mut debug = false;
+    let mut visualize = None;
+    let mut options = Options::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args.next().ok_or(anyhow!("--part needs a value"))?;
+                parts = match part.as_str() {
+                    "1" => vec![1],
+                    "2" => vec![2],
+                    p => bail!("Unknown part {}", p),
+                };
+            }
+            "--strict" => {
+                let value = args.next().ok_or(anyhow!("--strict needs a value"))?;
+                strict = value
+                    .parse()
+                    .map_err(|_| anyhow!("Bad --strict value {}", value))?;
+            }
+            "--debug" => debug = true,
+            "--visualize" => {
+                let dir = args
+                    .next()
+                    .ok_or(anyhow!("--visualize needs a directory"))?;
+                visualize = Some(PathBuf::from(dir));
+            }
+            a if a.starts_with("--") => {
+                let flag = flags
+                    .iter()
+                    .find(|f| a[2..] == *f.name)
+                    .ok_or_else(|| anyhow!("Unexpected argument {}", a))?;
+                let value = if flag.takes_value {
+                    let value = args.next().ok_or_else(|| anyhow!("{} needs a value", a))?;
+                    Some(value.to_owned())
+                } else {
+                    None
+                };
+                options.0.insert(flag.name, value);
+            }
+            a if input.is_none() => input = Some(a.to_owned()),
+            a => bail!("Unexpected argument {}", a),
+        }
+    }
+
+    Ok(DayArgs {
+        parts,
+        input,
+        strict,
+        debug,
+        visualize,
+        options,
+    })
+}
+
+/// Shared `main` of the day binaries: solves the requested parts of `day` on
+/// the given input and prints the answers.
+pub fn day_main(day: u8, part1: Solver, part2: Solver) -> Result<(), AnyhowError> {
+    day_main_with(day, part1, part2, &[], |_| Ok(()))
+}
+
+/// Like [`day_main`], also accepting the day specific `flags`: when any of
+/// them is given, `handler` runs instead of the solvers.
+pub fn day_main_with(
+    day: u8,
+    part1: Solver,
+    part2: Solver,
+    flags: &[Flag],
+    handler: Handler,
+) -> Result<(), AnyhowError> {
+    let run = setup(day, flags)?;
+
+    if !run.options.is_empty() {
+        return handler(&run).map_err(|e| parse::attach_file(e, &run.path));
+    }
+
+    let input = run.read_input()?;
+
+    for part in run.parts {
+        let answer = if part == 1 {
+            part1(&input)
+        } else {
+            part2(&input)
+        }
+        .map_err(|e| parse::attach_file(e, &run.path))?;
+        println!("Part {}: {}", part, answer);
+    }
+
+    Ok(())
+}
+
+/// Like [`day_main_with`], but `handler` always runs, flags or not: for the
+/// days solving the parts their own way, e.g. streaming the input rather than
+/// reading it whole.
+pub fn day_main_handler(day: u8, flags: &[Flag], handler: Handler) -> Result<(), AnyhowError> {
+    let run = setup(day, flags)?;
+
+    handler(&run).map_err(|e| parse::attach_file(e, &run.path))
+}
+
+/// Parses the command line and applies the common options, falling back to
+/// the default input of `day`.
+fn setup(day: u8, flags: &[Flag]) -> Result<DayRun, AnyhowError> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let DayArgs {
+        parts,
+        input: path,
+        strict,
+        debug,
+        visualize,
+        options,
+    } = parse_args(&args, flags)?;
+
+    parse::set_strict(strict);
+    debug::set_enabled(debug);
+    visual::set_output(visualize);
+    let path = match path {
+        Some(path) => path,
+        None => fetch::default_input(day, Path::new(input::DEFAULT_INPUT))?
+            .display()
+            .to_string(),
+    };
+
+    Ok(DayRun {
+        day,
+        parts,
+        path,
+        options,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAGS: [Flag; 2] = [Flag::switch("verbose"), Flag::value("top")];
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(|a| a.to_owned()).collect()
+    }
+
+    #[test]
+    fn parses_day_specific_flags() -> Result<(), AnyhowError> {
+        let parsed = parse_args(&args("--top 5 --part 2 --verbose in.txt"), &FLAGS)?;
+
+        assert_eq!(parsed.parts, [2]);
+        assert_eq!(parsed.input.as_deref(), Some("in.txt"));
+        assert!(parsed.options.is_set("verbose"));
+        assert_eq!(parsed.options.value("verbose"), None);
+        assert_eq!(parsed.options.parse::<usize>("top")?, Some(5));
+        assert_eq!(parsed.options.parse::<usize>("seed")?, None);
+
+        assert!(parse_args(&args("--visualize out"), &FLAGS)?
+            .options
+            .is_empty());
+        assert!(parse_args(&args("--debug"), &FLAGS)?.debug);
+        assert!(!parsed.debug);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_flags_and_bad_values() {
+        let err = |a: &str| parse_args(&args(a), &FLAGS).unwrap_err().to_string();
+
+        assert_eq!(err("--top 5 --seed 3"), "Unexpected argument --seed");
+        assert_eq!(err("--top"), "--top needs a value");
+        assert!(parse_args(&args("--verbose"), &[]).is_err());
+
+        let parsed = parse_args(&args("--top many"), &FLAGS).unwrap();
+        assert_eq!(
+            parsed
+                .options
+                .parse::<usize>("top")
+                .unwrap_err()
+                .to_string(),
+            "Bad --top value many: invalid digit found in string"
+        );
+    }
+}
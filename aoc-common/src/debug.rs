@@ -0,0 +1,29 @@
+//! Diagnostics of the solvers: intermediate values and rendered grids worth
+//! looking at when a day goes wrong.
+//!
+//! They go to stderr, and only once switched on with [`set_enabled`], so that
+//! solving stays quiet and its timings aren't spent on formatting.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Switches the diagnostics on or off for the whole process.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// `eprintln!` when diagnostics are on; the arguments aren't even evaluated
+/// otherwise.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::debug::is_enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
@@ -0,0 +1,273 @@
+//! Downloading of puzzle inputs into a per-user cache.
+//!
+//! Inputs are cached in `$AOC_CACHE_DIR`, or `aoc2022` under the user cache
+//! directory (`$XDG_CACHE_HOME`, `~/.cache` or `%LOCALAPPDATA%`). The session
+//! token is taken from `$AOC_SESSION`, or from a `session` file in the cache.
+
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+pub const YEAR: u16 = 2022;
+
+/// The Advent of Code site, which inputs are fetched from and answers
+/// submitted to.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Source of puzzle inputs.
+pub trait Fetcher {
+    fn fetch(&self, day: u8) -> Result<String, AnyhowError>;
+}
+
+/// Downloads inputs from the Advent of Code site with a session token.
+#[derive(Debug, Clone)]
+pub struct HttpFetcher {
+    session: String,
+    base_url: String,
+}
+
+impl HttpFetcher {
+    pub fn new(session: impl Into<String>) -> Self {
+        HttpFetcher {
+            session: session.into(),
+            base_url: BASE_URL.to_owned(),
+        }
+    }
+
+    /// Talks to another server than adventofcode.com, e.g. a local stub.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, day: u8) -> Result<String, AnyhowError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "aoc2022 input fetcher (aoc fetch)")
+            .call();
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| anyhow!("Can't read the input of day {}: {}", day, e)),
+            Err(ureq::Error::Status(404, _)) => bail!("Day {} is not unlocked yet", day),
+            Err(ureq::Error::Status(400 | 500, _)) => {
+                bail!("Input of day {} refused, is the session token valid?", day)
+            }
+            Err(e) => bail!("Can't fetch the input of day {}: {}", day, e),
+        }
+    }
+}
+
+/// Reads inputs from `dayN.txt` files of a directory, for tests and offline use.
+#[derive(Debug, Clone)]
+pub struct FixtureFetcher {
+    dir: PathBuf,
+}
+
+impl FixtureFetcher {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FixtureFetcher { dir: dir.into() }
+    }
+}
+
+impl Fetcher for FixtureFetcher {
+    fn fetch(&self, day: u8) -> Result<String, AnyhowError> {
+        let path = self.dir.join(format!("day{}.txt", day));
+        fs::read_to_string(&path).map_err(|e| anyhow!("Can't read {}: {}", path.display(), e))
+    }
+}
+
+/// Directory of cached inputs, one `dayN.txt` per day.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    /// The cache of the current user.
+    pub fn user() -> Result<Self, AnyhowError> {
+        if let Some(dir) = env::var_os("AOC_CACHE_DIR") {
+            return Ok(Cache::new(dir));
+        }
+
+        let base = env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+            .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+            .ok_or(anyhow!("No cache directory, set AOC_CACHE_DIR"))?;
+
+        Ok(Cache::new(base.join(format!("aoc{}", YEAR))))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{}.txt", day))
+    }
+
+    pub fn contains(&self, day: u8) -> bool {
+        self.path(day).exists()
+    }
+
+    /// Fetches the input of `day` into the cache, replacing any cached copy.
+    pub fn store(&self, day: u8, fetcher: &dyn Fetcher) -> Result<PathBuf, AnyhowError> {
+        let input = fetcher.fetch(day)?;
+
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| anyhow!("Can't create {}: {}", self.dir.display(), e))?;
+
+        let path = self.path(day);
+        fs::write(&path, input).map_err(|e| anyhow!("Can't write {}: {}", path.display(), e))?;
+
+        Ok(path)
+    }
+
+    /// Path of the cached input of `day`, fetching it first if missing.
+    pub fn get(&self, day: u8, fetcher: &dyn Fetcher) -> Result<PathBuf, AnyhowError> {
+        if self.contains(day) {
+            Ok(self.path(day))
+        } else {
+            self.store(day, fetcher)
+        }
+    }
+
+    /// The session token, from `$AOC_SESSION` or the `session` file.
+    pub fn session(&self) -> Result<String, AnyhowError> {
+        if let Some(session) = env::var("AOC_SESSION")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+        {
+            return Ok(session.trim().to_owned());
+        }
+
+        let path = self.dir.join("session");
+        fs::read_to_string(&path)
+            .map(|s| s.trim().to_owned())
+            .map_err(|_| {
+                anyhow!(
+                    "No session token, set AOC_SESSION or put it in {}",
+                    path.display()
+                )
+            })
+    }
+}
+
+/// Where the input of `day` is read from when no path is given: `local` if
+/// it exists, otherwise the user cache, downloading the input on a miss.
+pub fn default_input(day: u8, local: &Path) -> Result<PathBuf, AnyhowError> {
+    if local.exists() {
+        return Ok(local.to_owned());
+    }
+
+    let cache = Cache::user()?;
+    if cache.contains(day) {
+        return Ok(cache.path(day));
+    }
+
+    let session = cache.session().map_err(|e| {
+        anyhow!(
+            "No input at {} and day {} is not cached: {}",
+            local.display(),
+            day,
+            e
+        )
+    })?;
+
+    let path = cache.store(day, &HttpFetcher::new(session))?;
+    eprintln!("Fetched day {} input into {}", day, path.display());
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::stub_server;
+    use std::cell::Cell;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    struct Counting(Cell<usize>);
+
+    impl Fetcher for Counting {
+        fn fetch(&self, day: u8) -> Result<String, AnyhowError> {
+            self.0.set(self.0.get() + 1);
+            Ok(format!("input of day {}\n", day))
+        }
+    }
+
+    #[test]
+    fn cache_fetches_only_on_a_miss() -> Result<(), AnyhowError> {
+        let cache = Cache::new(temp_dir("cache"));
+        let fetcher = Counting(Cell::new(0));
+
+        let path = cache.get(3, &fetcher)?;
+        assert_eq!(fs::read_to_string(&path)?, "input of day 3\n");
+        assert_eq!(cache.get(3, &fetcher)?, path);
+        assert_eq!(fetcher.0.get(), 1);
+
+        cache.store(3, &fetcher)?;
+        assert_eq!(fetcher.0.get(), 2);
+
+        fs::remove_dir_all(cache.dir())?;
+        Ok(())
+    }
+
+    #[test]
+    fn fixture_fetcher_reads_day_files() -> Result<(), AnyhowError> {
+        let dir = temp_dir("fixture");
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("day7.txt"), "$ ls\n")?;
+
+        let fetcher = FixtureFetcher::new(&dir);
+        assert_eq!(fetcher.fetch(7)?, "$ ls\n");
+        assert!(fetcher.fetch(8).is_err());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn http_fetcher_sends_the_session() -> Result<(), AnyhowError> {
+        let (url, server) = stub_server("200 OK", "1000\n2000\n");
+
+        let input = HttpFetcher::new("s3cr3t").with_base_url(url).fetch(1)?;
+        assert_eq!(input, "1000\n2000\n");
+
+        let (head, _) = server.join().unwrap();
+        assert!(head.starts_with("GET /2022/day/1/input "));
+        assert!(head.contains("session=s3cr3t"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn http_fetcher_reports_locked_days() {
+        let (url, server) = stub_server("404 Not Found", "");
+
+        let err = HttpFetcher::new("s3cr3t")
+            .with_base_url(url)
+            .fetch(25)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Day 25 is not unlocked yet");
+
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{input::read_input, Solver};
+use anyhow::{anyhow, Error as AnyhowError};
+use std::path::Path;
+
+/// File next to each day's `input.txt` holding the accepted answers.
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// Looks up the answer for `part` in the contents of an answers file.
+///
+/// The file uses the same layout as a day binary prints: a `Part N: answer`
+/// line per part, with multiline answers continuing on the lines after it.
+pub fn find_answer(answers: &str, part: u8) -> Option<String> {
+    let header = format!("Part {}:", part);
+    let mut lines = answers.lines().skip_while(|l| !l.starts_with(&header));
+
+    let first = lines.next()?[header.len()..].trim();
+    let rest = lines.take_while(|l| !l.starts_with("Part "));
+
+    let answer = (!first.is_empty())
+        .then_some(first)
+        .into_iter()
+        .chain(rest)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(answer.trim_end().to_owned())
+}
+
+/// Runs `solver` on `input.txt` in the day directory `dir` and checks the
+/// result against the answer recorded in its answers file.
+pub fn check(dir: impl AsRef<Path>, part: u8, solver: Solver) -> Result<(), AnyhowError> {
+    let dir = dir.as_ref();
+    let answers = read_input(dir.join(ANSWERS_FILE))?;
+    let expected = find_answer(&answers, part).ok_or(anyhow!(
+        "No answer for part {} in {}",
+        part,
+        ANSWERS_FILE
+    ))?;
+
+    let answer = solver(&read_input(dir.join("input.txt"))?)?.to_string();
+    if answer.trim_end() != expected {
+        return Err(anyhow!(
+            "Part {} changed:\nexpected:\n{}\ngot:\n{}",
+            part,
+            expected,
+            answer
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_single_line_answers() {
+        let answers = "Part 1: 42\nPart 2: GFTN\n";
+
+        assert_eq!(find_answer(answers, 1).as_deref(), Some("42"));
+        assert_eq!(find_answer(answers, 2).as_deref(), Some("GFTN"));
+        assert_eq!(find_answer(answers, 3), None);
+    }
+
+    #[test]
+    fn finds_multiline_answers() {
+        let answers = "Part 1: 1\nPart 2:\n#..\n.#.\n\n";
+
+        assert_eq!(find_answer(answers, 2).as_deref(), Some("#..\n.#."));
+    }
+}
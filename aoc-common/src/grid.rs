@@ -0,0 +1,450 @@
+use crate::{LineError, ParseReport};
+use anyhow::{anyhow, Error as AnyhowError};
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+/// Signed 2D coordinates: `x` grows to the right, `y` down the rows.
+pub type Pos = (isize, isize);
+
+/// Signed 3D coordinates.
+pub type Voxel = (isize, isize, isize);
+
+/// Offsets of the four orthogonal neighbors: up, right, down, left.
+pub const NEIGHBORS_4: [Pos; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbors, clockwise from the top left one.
+pub const NEIGHBORS_8: [Pos; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// Offsets of the six voxels sharing a face.
+pub const NEIGHBORS_6: [Voxel; 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+/// The four orthogonal neighbors of `pos`, with no bounds to respect.
+pub fn neighbors4(pos: Pos) -> impl Iterator<Item = Pos> {
+    NEIGHBORS_4
+        .into_iter()
+        .map(move |(dx, dy)| (pos.0 + dx, pos.1 + dy))
+}
+
+/// All eight neighbors of `pos`, with no bounds to respect.
+pub fn neighbors8(pos: Pos) -> impl Iterator<Item = Pos> {
+    NEIGHBORS_8
+        .into_iter()
+        .map(move |(dx, dy)| (pos.0 + dx, pos.1 + dy))
+}
+
+/// The six face neighbors of `voxel`, with no bounds to respect.
+pub fn neighbors6(voxel: Voxel) -> impl Iterator<Item = Voxel> {
+    NEIGHBORS_6
+        .into_iter()
+        .map(move |(dx, dy, dz)| (voxel.0 + dx, voxel.1 + dy, voxel.2 + dz))
+}
+
+/// A dense rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Adds a row filled with `fill` below the last one.
+    pub fn push_row(&mut self, fill: T) {
+        self.cells.resize(self.cells.len() + self.width, fill);
+        self.height += 1;
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid out of equally long rows.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self, AnyhowError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for row in rows {
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(anyhow!(
+                    "Row {} has {} cells instead of {}",
+                    height + 1,
+                    row.len(),
+                    expected
+                ));
+            }
+
+            cells.extend(row);
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a character map, one row per non-empty line. `cell` turns a
+    /// character into a cell, or rejects it, in which case `expected`
+    /// describes what should have been there. Bad rows fail the parse even
+    /// outside strict mode, as skipping one would shift every row below it.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, AnyhowError> {
+        let mut rows = vec![];
+        let mut errors = vec![];
+
+        for (i, s) in input.lines().enumerate() {
+            if s.trim().is_empty() {
+                continue;
+            }
+
+            let row = s
+                .char_indices()
+                .map(|(i, c)| cell(c).ok_or(LineError::at(s, &s[i..], expected)))
+                .collect::<Result<Vec<_>, _>>();
+            match row {
+                Ok(row) => rows.push(row),
+                Err(e) => errors.extend(ParseReport::single(i, s, e).errors),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(ParseReport { errors }.into());
+        }
+
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, (x, y): Pos) -> Option<usize> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+
+        Some(y as usize * self.width + x as usize)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    /// The cell at `pos`, or `None` when it lies outside of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width as isize;
+        (0..self.height as isize).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The orthogonal neighbors of `pos` inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        neighbors4(pos).filter(|&p| self.contains(p))
+    }
+
+    /// All the neighbors of `pos` inside the grid, diagonals included.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        neighbors8(pos).filter(|&p| self.contains(p))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid as text, one line per row.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> Vec<String> {
+        self.rows()
+            .map(|row| row.iter().map(&cell).collect::<String>())
+            .collect()
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("{:?} is outside of the grid", pos),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} is outside of the grid", pos),
+        }
+    }
+}
+
+/// A grid with no fixed bounds that only stores the cells set so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(&pos, v)| (pos, v))
+    }
+
+    /// Top left and bottom right corners of the smallest box holding every
+    /// cell, or `None` for an empty grid.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let mut positions = self.cells.keys();
+        let &first = positions.next()?;
+
+        Some(positions.fold((first, first), |(min, max), &(x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        }))
+    }
+
+    /// Draws the bounding box of the cells as text; positions without a
+    /// cell are drawn as `empty`.
+    pub fn render(&self, empty: char, cell: impl Fn(&T) -> char) -> Vec<String> {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.bounds() else {
+            return vec![];
+        };
+
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| self.get((x, y)).map_or(empty, &cell))
+                    .collect::<String>()
+            })
+            .collect()
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: HashMap::from_iter(iter),
+        }
+    }
+}
+
+/// A dense box of voxels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid3<T> {
+    size: (usize, usize, usize),
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid3<T> {
+    pub fn new(width: usize, height: usize, depth: usize, fill: T) -> Self {
+        Grid3 {
+            size: (width, height, depth),
+            cells: vec![fill; width * height * depth],
+        }
+    }
+}
+
+impl<T> Grid3<T> {
+    /// Width, height and depth of the box.
+    pub fn size(&self) -> (usize, usize, usize) {
+        self.size
+    }
+
+    fn index_of(&self, (x, y, z): Voxel) -> Option<usize> {
+        let (w, h, d) = self.size;
+        if x < 0 || y < 0 || z < 0 || x as usize >= w || y as usize >= h || z as usize >= d {
+            return None;
+        }
+
+        Some((z as usize * h + y as usize) * w + x as usize)
+    }
+
+    pub fn contains(&self, voxel: Voxel) -> bool {
+        self.index_of(voxel).is_some()
+    }
+
+    /// The voxel at `voxel`, or `None` when it lies outside of the box.
+    pub fn get(&self, voxel: Voxel) -> Option<&T> {
+        self.index_of(voxel).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, voxel: Voxel) -> Option<&mut T> {
+        self.index_of(voxel).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position of the box.
+    pub fn positions(&self) -> impl Iterator<Item = Voxel> {
+        let (w, h, d) = self.size;
+        (0..d as isize).flat_map(move |z| {
+            (0..h as isize).flat_map(move |y| (0..w as isize).map(move |x| (x, y, z)))
+        })
+    }
+
+    /// Every voxel with its position.
+    pub fn iter(&self) -> impl Iterator<Item = (Voxel, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The face neighbors of `voxel` inside the box.
+    pub fn neighbors6(&self, voxel: Voxel) -> impl Iterator<Item = Voxel> + '_ {
+        neighbors6(voxel).filter(|&v| self.contains(v))
+    }
+}
+
+impl<T> Index<Voxel> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, voxel: Voxel) -> &Self::Output {
+        match self.index_of(voxel) {
+            Some(i) => &self.cells[i],
+            None => panic!("{:?} is outside of the grid", voxel),
+        }
+    }
+}
+
+impl<T> IndexMut<Voxel> for Grid3<T> {
+    fn index_mut(&mut self, voxel: Voxel) -> &mut Self::Output {
+        match self.index_of(voxel) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} is outside of the grid", voxel),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_renders_char_maps() {
+        let grid = Grid::parse("#.#\n.#.\n", "# or .", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[(1, 1)]);
+        assert_eq!(grid.get((-1, 0)), None);
+        assert_eq!(
+            grid.render(|&b| if b { '#' } else { '.' }),
+            vec!["#.#", ".#."]
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn neighbors_stay_inside_the_grid() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(grid.neighbors4((0, 0)).count(), 2);
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+
+        let voxels = Grid3::new(2, 2, 2, 0);
+        assert_eq!(voxels.neighbors6((0, 0, 0)).count(), 3);
+        assert_eq!(neighbors6((0, 0, 0)).count(), 6);
+    }
+
+    #[test]
+    fn sparse_grids_render_their_bounding_box() {
+        let grid: SparseGrid<char> = [((-1, 2), '#'), ((1, 3), 'o')].into_iter().collect();
+
+        assert_eq!(grid.bounds(), Some(((-1, 2), (1, 3))));
+        assert_eq!(grid.render('.', |&c| c), vec!["#..", "..o"]);
+    }
+}
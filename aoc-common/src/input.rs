@@ -0,0 +1,24 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+/// Input file each day reads when no path is given.
+pub const DEFAULT_INPUT: &str = "./input.txt";
+
+/// Reads the puzzle input from a file, or from stdin when the path is `-`.
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, AnyhowError> {
+    let path = path.as_ref();
+
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| anyhow!("Can't read stdin: {}", e))?;
+        return Ok(input);
+    }
+
+    fs::read_to_string(path).map_err(|e| anyhow!("Can't read {}: {}", path.display(), e))
+}
@@ -0,0 +1,45 @@
+//! Helpers shared by the day crates.
+
+mod answer;
+mod day_main;
+pub mod debug;
+pub mod fetch;
+pub mod golden;
+pub mod grid;
+pub mod input;
+pub mod parse;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+pub mod visual;
+
+pub use answer::Answer;
+pub use day_main::{day_main, day_main_handler, day_main_with, DayRun, Flag, Handler, Options};
+pub use grid::{Grid, Grid3, SparseGrid};
+pub use input::read_input;
+pub use parse::{LineError, ParseReport};
+
+/// Entry point of a single puzzle part: takes the raw input, returns the answer.
+pub type Solver = fn(&str) -> anyhow::Result<Answer>;
+
+/// Splits a vector into independent mutable references to each of its
+/// elements, so that two of them can be borrowed mutably at the same time.
+pub fn borrow_mut_elementwise<T>(v: &mut [T]) -> Vec<&mut T> {
+    let mut result: Vec<&mut T> = Vec::new();
+    let mut current: &mut [T];
+    let mut rest = v;
+    while !rest.is_empty() {
+        (current, rest) = rest.split_at_mut(1);
+        result.push(&mut current[0]);
+    }
+    result
+}
+
+/// Turns rows of characters into printable lines, e.g. for printing a grid.
+pub fn render_rows<R>(rows: impl IntoIterator<Item = R>) -> Vec<String>
+where
+    R: IntoIterator<Item = char>,
+{
+    rows.into_iter()
+        .map(|r| r.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+}
@@ -0,0 +1,184 @@
+use anyhow::Error as AnyhowError;
+use std::{
+    error::Error,
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static STRICT: AtomicBool = AtomicBool::new(true);
+
+/// Switches strict parsing on or off for the whole process. In strict mode
+/// (the default) any malformed line fails the run; otherwise such lines are
+/// reported on stderr and skipped.
+pub fn set_strict(strict: bool) {
+    STRICT.store(strict, Ordering::Relaxed);
+}
+
+pub fn is_strict() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
+
+/// What went wrong within a single line, as returned by the day parsers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// 1-based column where the unexpected text starts.
+    pub column: usize,
+    pub expected: String,
+}
+
+impl LineError {
+    pub fn new(column: usize, expected: impl Into<String>) -> Self {
+        LineError {
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Error pointing at `part`, which must be a slice of `line`.
+    pub fn at(line: &str, part: &str, expected: impl Into<String>) -> Self {
+        LineError::new(column_of(line, part), expected)
+    }
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "column {}: expected {}", self.column, self.expected)
+    }
+}
+
+impl Error for LineError {}
+
+/// 1-based column at which `part`, a slice of `line`, starts.
+pub fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line[..offset.min(line.len())].chars().count() + 1
+}
+
+/// A malformed line of an input file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    /// 1-based line number.
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub text: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: expected {}, found {:?}",
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            self.expected,
+            self.text
+        )
+    }
+}
+
+/// Every malformed line found while parsing an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseReport {
+    pub errors: Vec<ParseError>,
+}
+
+impl ParseReport {
+    /// Report for a single line-level error; `index` is the 0-based line index.
+    pub fn single(index: usize, text: &str, error: LineError) -> Self {
+        ParseReport {
+            errors: vec![ParseError {
+                file: None,
+                line: index + 1,
+                column: error.column,
+                expected: error.expected,
+                text: text.to_owned(),
+            }],
+        }
+    }
+
+    /// Names `file` as the origin of the errors not already attributed to
+    /// another one, e.g. a file the input itself refers to.
+    pub fn with_file(mut self, file: &str) -> Self {
+        for e in &mut self.errors {
+            e.file.get_or_insert_with(|| file.to_owned());
+        }
+        self
+    }
+}
+
+impl Display for ParseReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let file = self
+            .errors
+            .first()
+            .and_then(|e| e.file.as_deref())
+            .unwrap_or("input");
+        writeln!(f, "{} malformed line(s) in {}:", self.errors.len(), file)?;
+        for e in &self.errors {
+            writeln!(f, "  {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseReport {}
+
+/// Names the input file in a parse report carried by `err`, if any.
+pub fn attach_file(err: AnyhowError, file: &str) -> AnyhowError {
+    let file = if file == "-" { "<stdin>" } else { file };
+
+    match err.downcast::<ParseReport>() {
+        Ok(report) => report.with_file(file).into(),
+        Err(err) => err,
+    }
+}
+
+/// Parses numbered lines (0-based indices), skipping empty ones. Every bad
+/// line is collected; in strict mode they fail the parse, otherwise they are
+/// reported on stderr and dropped.
+pub fn parse_numbered<'a, T>(
+    lines: impl IntoIterator<Item = (usize, &'a str)>,
+    parse: impl Fn(&'a str) -> Result<T, LineError>,
+) -> Result<Vec<T>, ParseReport> {
+    let mut parsed = vec![];
+    let mut errors = vec![];
+
+    for (i, line) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse(line) {
+            Ok(v) => parsed.push(v),
+            Err(e) => errors.extend(ParseReport::single(i, line, e).errors),
+        }
+    }
+
+    check(parsed, errors)
+}
+
+/// Parses every non-empty line of the input, see [`parse_numbered`].
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    parse: impl Fn(&'a str) -> Result<T, LineError>,
+) -> Result<Vec<T>, ParseReport> {
+    parse_numbered(input.lines().enumerate(), parse)
+}
+
+/// Applies the strictness setting to the outcome of a parse.
+pub fn check<T>(parsed: T, errors: Vec<ParseError>) -> Result<T, ParseReport> {
+    if errors.is_empty() {
+        return Ok(parsed);
+    }
+
+    let report = ParseReport { errors };
+    if is_strict() {
+        Err(report)
+    } else {
+        eprint!("Skipping {}", report);
+        Ok(parsed)
+    }
+}
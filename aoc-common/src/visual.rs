@@ -0,0 +1,367 @@
+//! Rendering of simulation states to PNG stills and animated GIFs.
+//!
+//! Nothing is rendered until an output directory is set with [`set_output`];
+//! until then a [`Recorder`] never even builds its frames.
+
+use crate::SparseGrid;
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+static OUTPUT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Sets the directory visualizations are written to, `None` turns them off.
+pub fn set_output(dir: Option<PathBuf>) {
+    *OUTPUT.lock().expect("output lock") = dir;
+}
+
+pub fn output() -> Option<PathBuf> {
+    OUTPUT.lock().expect("output lock").clone()
+}
+
+pub type Rgb = [u8; 3];
+
+/// Color drawn for characters missing from the palette.
+const UNKNOWN: Rgb = [255, 0, 255];
+
+/// A picture of the simulation state, one character per cell. Positions may
+/// be negative; frames of an animation are aligned on their coordinates.
+pub type Frame = SparseGrid<char>;
+
+/// Builds a frame out of rendered rows, the first row being at the top.
+pub fn frame_from_rows<R>(rows: impl IntoIterator<Item = R>) -> Frame
+where
+    R: IntoIterator<Item = char>,
+{
+    rows.into_iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.into_iter()
+                .enumerate()
+                .map(move |(x, c)| ((x as isize, y as isize), c))
+        })
+        .collect()
+}
+
+/// Colors of the cell characters. Cells missing from a frame get the
+/// background color, characters without a color are drawn in magenta.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    chars: Vec<char>,
+    colors: Vec<Rgb>,
+}
+
+impl Palette {
+    pub fn new(background: Rgb) -> Self {
+        Palette {
+            chars: vec![],
+            colors: vec![background, UNKNOWN],
+        }
+    }
+
+    /// Adds the color of `c`. A palette holds at most 254 characters.
+    pub fn with(mut self, c: char, color: Rgb) -> Self {
+        assert!(self.colors.len() < 256, "Too many colors in the palette");
+
+        self.chars.push(c);
+        self.colors.push(color);
+        self
+    }
+
+    fn index(&self, c: char) -> u8 {
+        self.chars
+            .iter()
+            .position(|&p| p == c)
+            .map_or(1, |i| i as u8 + 2)
+    }
+
+    fn flat(&self) -> Vec<u8> {
+        self.colors.concat()
+    }
+}
+
+/// Frames drawn as palette indices on a canvas fitting all of them.
+#[derive(Debug)]
+struct Canvas {
+    width: usize,
+    height: usize,
+    frames: Vec<Vec<u8>>,
+}
+
+fn rasterize(frames: &[Frame], palette: &Palette, scale: usize) -> Result<Canvas, AnyhowError> {
+    let (min, max) = frames
+        .iter()
+        .filter_map(|f| f.bounds())
+        .reduce(|(a_min, a_max), (b_min, b_max)| {
+            (
+                (a_min.0.min(b_min.0), a_min.1.min(b_min.1)),
+                (a_max.0.max(b_max.0), a_max.1.max(b_max.1)),
+            )
+        })
+        .ok_or(anyhow!("Nothing to draw"))?;
+
+    let width = (max.0 - min.0 + 1) as usize * scale;
+    let height = (max.1 - min.1 + 1) as usize * scale;
+
+    let frames = frames
+        .iter()
+        .map(|frame| {
+            let mut pixels = vec![0u8; width * height];
+            for ((x, y), &c) in frame.iter() {
+                let index = palette.index(c);
+                let left = (x - min.0) as usize * scale;
+                let top = (y - min.1) as usize * scale;
+
+                for row in top..top + scale {
+                    pixels[row * width + left..row * width + left + scale].fill(index);
+                }
+            }
+            pixels
+        })
+        .collect();
+
+    Ok(Canvas {
+        width,
+        height,
+        frames,
+    })
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>, AnyhowError> {
+    let file = File::create(path).map_err(|e| anyhow!("Can't write {}: {}", path.display(), e))?;
+    Ok(BufWriter::new(file))
+}
+
+/// Writes `frame` as a PNG image with `scale`×`scale` pixels per cell.
+pub fn write_png(
+    path: &Path,
+    frame: &Frame,
+    palette: &Palette,
+    scale: usize,
+) -> Result<(), AnyhowError> {
+    let canvas = rasterize(std::slice::from_ref(frame), palette, scale)?;
+
+    let mut encoder = png::Encoder::new(create(path)?, canvas.width as u32, canvas.height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette.flat());
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&canvas.frames[0])?;
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Writes `frames` as a looping GIF animation showing each frame for
+/// `delay_ms` milliseconds.
+pub fn write_gif(
+    path: &Path,
+    frames: &[Frame],
+    palette: &Palette,
+    scale: usize,
+    delay_ms: u16,
+) -> Result<(), AnyhowError> {
+    let canvas = rasterize(frames, palette, scale)?;
+
+    let (width, height) = match (u16::try_from(canvas.width), u16::try_from(canvas.height)) {
+        (Ok(w), Ok(h)) => (w, h),
+        _ => bail!(
+            "{}x{} pixels is too large for a GIF",
+            canvas.width,
+            canvas.height
+        ),
+    };
+
+    let mut encoder = gif::Encoder::new(create(path)?, width, height, &palette.flat())?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for pixels in &canvas.frames {
+        encoder.write_frame(&gif::Frame {
+            width,
+            height,
+            delay: delay_ms / 10,
+            buffer: Cow::Borrowed(pixels),
+            ..gif::Frame::default()
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Collects the frames of a simulation run. When finished, the last frame is
+/// written to `<name>.png` and, if there are several, all of them to
+/// `<name>.gif` in the output directory.
+#[derive(Debug)]
+pub struct Recorder {
+    name: String,
+    palette: Palette,
+    dir: Option<PathBuf>,
+    scale: usize,
+    sampling: usize,
+    delay_ms: u16,
+    steps: usize,
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    pub fn new(name: impl Into<String>, palette: Palette) -> Self {
+        Recorder {
+            name: name.into(),
+            palette,
+            dir: output(),
+            scale: 4,
+            sampling: 1,
+            delay_ms: 50,
+            steps: 0,
+            frames: vec![],
+        }
+    }
+
+    /// A recorder of `part` of `day`, named `day<N>-part<P>` like every
+    /// other day's.
+    pub fn for_part(day: u8, part: u8, palette: Palette) -> Self {
+        Recorder::new(format!("day{}-part{}", day, part), palette)
+    }
+
+    /// Pixels per cell side, 4 by default.
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Keeps only every `n`th step.
+    pub fn with_sampling(mut self, n: usize) -> Self {
+        self.sampling = n.max(1);
+        self
+    }
+
+    /// Time each frame of the animation is shown, 50ms by default.
+    pub fn with_delay(mut self, delay_ms: u16) -> Self {
+        self.delay_ms = delay_ms;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    /// Records a step of the simulation; `frame` is only called for the
+    /// steps that are kept.
+    pub fn step(&mut self, frame: impl FnOnce() -> Frame) {
+        if self.is_enabled() && self.steps.is_multiple_of(self.sampling) {
+            self.frames.push(frame());
+        }
+        self.steps += 1;
+    }
+
+    /// Records the final state, regardless of the sampling.
+    pub fn last(&mut self, frame: impl FnOnce() -> Frame) {
+        if self.is_enabled() {
+            self.frames.push(frame());
+        }
+    }
+
+    /// Writes out the recorded frames, returning the files written.
+    pub fn finish(self) -> Result<Vec<PathBuf>, AnyhowError> {
+        let (dir, last) = match (&self.dir, self.frames.last()) {
+            (Some(dir), Some(last)) => (dir, last),
+            _ => return Ok(vec![]),
+        };
+
+        fs::create_dir_all(dir).map_err(|e| anyhow!("Can't create {}: {}", dir.display(), e))?;
+
+        let png = dir.join(format!("{}.png", self.name));
+        write_png(&png, last, &self.palette, self.scale)?;
+        let mut written = vec![png];
+
+        if self.frames.len() > 1 {
+            let gif = dir.join(format!("{}.gif", self.name));
+            write_gif(&gif, &self.frames, &self.palette, self.scale, self.delay_ms)?;
+            written.push(gif);
+        }
+
+        for path in &written {
+            eprintln!("Wrote {}", path.display());
+        }
+
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> Palette {
+        Palette::new([0, 0, 0])
+            .with('#', [255, 255, 255])
+            .with('o', [255, 200, 0])
+    }
+
+    #[test]
+    fn palette_indices_skip_background_and_unknown() {
+        let palette = palette();
+
+        assert_eq!(palette.index('#'), 2);
+        assert_eq!(palette.index('o'), 3);
+        assert_eq!(palette.index('?'), 1);
+        assert_eq!(palette.flat().len(), 4 * 3);
+    }
+
+    #[test]
+    fn frames_are_aligned_on_a_shared_canvas() {
+        let first = frame_from_rows(["#o".chars()]);
+        let mut second = Frame::new();
+        second.insert((-1, 1), '#');
+
+        let canvas = rasterize(&[first, second], &palette(), 2).unwrap();
+
+        assert_eq!((canvas.width, canvas.height), (6, 4));
+        #[rustfmt::skip]
+        assert_eq!(canvas.frames[0], vec![
+            0, 0, 2, 2, 3, 3,
+            0, 0, 2, 2, 3, 3,
+            0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0,
+        ]);
+        #[rustfmt::skip]
+        assert_eq!(canvas.frames[1], vec![
+            0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0,
+            2, 2, 0, 0, 0, 0,
+            2, 2, 0, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn recorder_writes_png_and_gif() -> Result<(), AnyhowError> {
+        let dir = std::env::temp_dir().join(format!("aoc-visual-{}", std::process::id()));
+
+        let mut recorder = Recorder::new("test", palette()).with_sampling(2);
+        recorder.dir = Some(dir.clone());
+        for i in 0..4 {
+            recorder.step(|| [((i, 0), '#')].into_iter().collect());
+        }
+        recorder.last(|| frame_from_rows(["o#o#".chars()]));
+        assert_eq!(recorder.frames.len(), 3);
+
+        let written = recorder.finish()?;
+        assert_eq!(written, vec![dir.join("test.png"), dir.join("test.gif")]);
+        assert!(fs::read(&written[0])?.starts_with(b"\x89PNG"));
+        assert!(fs::read(&written[1])?.starts_with(b"GIF89a"));
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn recordings_of_parts_share_a_naming_scheme() {
+        assert_eq!(Recorder::for_part(9, 2, palette()).name, "day9-part2");
+    }
+}
@@ -0,0 +1,20 @@
+//! Runs in its own process, as strictness is a global setting the unit tests
+//! rely on being on.
+
+use aoc_common::{parse, Grid, ParseReport};
+
+#[test]
+fn grids_never_skip_rows() {
+    parse::set_strict(false);
+
+    let report = Grid::parse("#.\nx.\n.#\n", "# or .", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .unwrap_err()
+    .downcast::<ParseReport>()
+    .unwrap();
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!((report.errors[0].line, report.errors[0].column), (2, 1));
+}
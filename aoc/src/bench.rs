@@ -0,0 +1,243 @@
+use crate::{
+    days::{self, Day},
+    run::default_input,
+};
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{parse, read_input};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Day to benchmark; every day is benchmarked when omitted
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Runs of each phase; the fastest one is kept
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    samples: u32,
+
+    /// Baseline file, written on the first run and compared against afterwards
+    #[arg(long, default_value = "bench-baseline.json")]
+    baseline: PathBuf,
+
+    /// Slowdown over the baseline, in percent, flagged as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// Overwrite the baseline with the timings of this run
+    #[arg(long)]
+    save: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part 1"),
+            Phase::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+const PHASES: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+/// Best time of a phase of a day. Part timings include parsing the input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Timing {
+    day: u8,
+    phase: Phase,
+    elapsed_ms: f64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Baseline {
+    timings: Vec<Timing>,
+}
+
+impl Baseline {
+    fn load(path: &Path) -> Result<Option<Self>, AnyhowError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let json = read_input(path)?;
+        let baseline = serde_json::from_str(&json)
+            .map_err(|e| anyhow!("Bad baseline {}: {}", path.display(), e))?;
+
+        Ok(Some(baseline))
+    }
+
+    fn save(&self, path: &Path) -> Result<(), AnyhowError> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n").map_err(|e| anyhow!("Can't write {}: {}", path.display(), e))
+    }
+
+    fn find(&self, day: u8, phase: Phase) -> Option<&Timing> {
+        self.timings
+            .iter()
+            .find(|t| t.day == day && t.phase == phase)
+    }
+
+    /// Replaces the timings of the days present in `timings`.
+    fn update(&mut self, timings: &[Timing]) {
+        self.timings
+            .retain(|t| !timings.iter().any(|new| new.day == t.day));
+        self.timings.extend_from_slice(timings);
+        self.timings.sort_by_key(|t| (t.day, t.phase as u8));
+    }
+}
+
+fn measure(
+    samples: u32,
+    mut f: impl FnMut() -> Result<(), AnyhowError>,
+) -> Result<Duration, AnyhowError> {
+    let mut best = Duration::MAX;
+    for _ in 0..samples {
+        let started = Instant::now();
+        f()?;
+        best = best.min(started.elapsed());
+    }
+
+    Ok(best)
+}
+
+fn bench_day(day: &Day, samples: u32) -> Result<Vec<Timing>, AnyhowError> {
+    let path = default_input(day)?;
+    let input = read_input(&path)?;
+
+    PHASES
+        .iter()
+        .map(|&phase| {
+            let elapsed = measure(samples, || match phase {
+                Phase::Parse => (day.parse)(&input),
+                Phase::Part1 => (day.part1)(&input).map(drop),
+                Phase::Part2 => (day.part2)(&input).map(drop),
+            })
+            .map_err(|e| parse::attach_file(e, &path.display().to_string()))?;
+
+            Ok(Timing {
+                day: day.number,
+                phase,
+                elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            })
+        })
+        .collect()
+}
+
+/// Slowdown of `timing` over `base` in percent, negative when faster.
+fn slowdown(timing: &Timing, base: &Timing) -> f64 {
+    (timing.elapsed_ms / base.elapsed_ms - 1.0) * 100.0
+}
+
+pub fn bench(args: BenchArgs) -> Result<(), AnyhowError> {
+    let selected: Vec<&Day> = match args.day {
+        Some(number) => {
+            vec![days::find(number).ok_or(anyhow!("Day {} is not implemented", number))?]
+        }
+        None => days::DAYS.iter().collect(),
+    };
+
+    let baseline = Baseline::load(&args.baseline)?;
+
+    let mut timings = vec![];
+    let mut regressions = 0;
+    for day in selected {
+        for timing in bench_day(day, args.samples)? {
+            let base = baseline
+                .as_ref()
+                .and_then(|b| b.find(timing.day, timing.phase));
+
+            let mut line = format!(
+                "Day {}, {}: {:.3}ms",
+                timing.day, timing.phase, timing.elapsed_ms
+            );
+            if let Some(base) = base {
+                let change = slowdown(&timing, base);
+                line += &format!(" (baseline {:.3}ms, {:+.1}%)", base.elapsed_ms, change);
+                if change > args.threshold {
+                    line += " REGRESSION";
+                    regressions += 1;
+                }
+            }
+            println!("{}", line);
+
+            timings.push(timing);
+        }
+    }
+
+    if baseline.is_none() || args.save {
+        let mut baseline = baseline.unwrap_or_default();
+        baseline.update(&timings);
+        baseline.save(&args.baseline)?;
+        println!("Baseline written to {}", args.baseline.display());
+    }
+
+    if regressions > 0 {
+        bail!(
+            "{} phase(s) more than {}% slower than the baseline",
+            regressions,
+            args.threshold
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, phase: Phase, elapsed_ms: f64) -> Timing {
+        Timing {
+            day,
+            phase,
+            elapsed_ms,
+        }
+    }
+
+    #[test]
+    fn slowdown_is_relative_to_the_baseline() {
+        let base = timing(1, Phase::Parse, 2.0);
+
+        assert_eq!(slowdown(&timing(1, Phase::Parse, 3.0), &base), 50.0);
+        assert_eq!(slowdown(&timing(1, Phase::Parse, 1.0), &base), -50.0);
+    }
+
+    #[test]
+    fn update_replaces_only_benchmarked_days() {
+        let mut baseline = Baseline {
+            timings: vec![timing(1, Phase::Parse, 1.0), timing(2, Phase::Parse, 2.0)],
+        };
+
+        baseline.update(&[timing(2, Phase::Parse, 3.0), timing(2, Phase::Part1, 4.0)]);
+
+        let ms = baseline
+            .timings
+            .iter()
+            .map(|t| (t.day, t.phase, t.elapsed_ms))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ms,
+            vec![
+                (1, Phase::Parse, 1.0),
+                (2, Phase::Parse, 3.0),
+                (2, Phase::Part1, 4.0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use aoc_common::Solver;
+
+/// Parsing step of a day, with the parsed input thrown away.
+pub type Parser = fn(&str) -> anyhow::Result<()>;
+
+/// A registered puzzle day with its parser and two part solvers.
+pub struct Day {
+    pub number: u8,
+    pub parse: Parser,
+    pub part1: Solver,
+    pub part2: Solver,
+}
+
+impl Day {
+    pub fn solver(&self, part: u8) -> Solver {
+        match part {
+            1 => self.part1,
+            _ => self.part2,
+        }
+    }
+}
+
+macro_rules! day {
+    ($number:literal, $day:ident) => {
+        Day {
+            number: $number,
+            parse: |input| $day::parse(input).map(drop),
+            part1: $day::solve_part1,
+            part2: $day::solve_part2,
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(1, day1),
+    day!(2, day2),
+    day!(3, day3),
+    day!(4, day4),
+    day!(5, day5),
+    day!(6, day6),
+    day!(7, day7),
+    day!(8, day8),
+    day!(9, day9),
+    day!(10, day10),
+    day!(11, day11),
+    day!(12, day12),
+    day!(13, day13),
+    day!(14, day14),
+    day!(15, day15),
+    day!(16, day16),
+    day!(17, day17),
+    day!(18, day18),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
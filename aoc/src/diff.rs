@@ -0,0 +1,209 @@
+//! Differential testing of the fast solvers against the brute-force
+//! reference ones, on small random inputs.
+
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::Answer;
+use clap::Args;
+use proptest::{
+    prelude::*,
+    strategy::ValueTree,
+    test_runner::{Config, RngAlgorithm, TestRng, TestRunner},
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// Day to check; every day with a reference solver when omitted
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Number of random inputs to try per check
+    #[arg(long, default_value_t = 100)]
+    cases: u32,
+
+    /// Seed of the random inputs, to reproduce an earlier run
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+/// A random input and the parameter both solutions are given.
+type Case = (String, usize);
+
+type Solution = fn(&str, usize) -> Result<Answer, AnyhowError>;
+
+/// A fast solution and the reference it must agree with.
+struct Check {
+    day: u8,
+    name: &'static str,
+    /// What the parameter of the solutions is, if they take one
+    param: Option<&'static str>,
+    cases: fn() -> BoxedStrategy<Case>,
+    fast: Solution,
+    reference: Solution,
+}
+
+static CHECKS: &[Check] = &[
+    Check {
+        day: 15,
+        name: "covered positions in a row",
+        param: Some("y"),
+        cases: || {
+            (day15::gen::readings(20), 0..=20usize)
+                .prop_map(|(readings, y)| (day15::gen::to_input(&readings), y))
+                .boxed()
+        },
+        fast: |input, y| Ok(day15::count_covered(input, y as isize)?.into()),
+        reference: |input, y| Ok(day15::reference::count_covered(input, y as isize)?.into()),
+    },
+    Check {
+        day: 15,
+        name: "distress beacon",
+        param: Some("max"),
+        cases: || {
+            day15::gen::with_hole(20)
+                .prop_map(|readings| (day15::gen::to_input(&readings), 20))
+                .boxed()
+        },
+        fast: |input, max| Ok(day15::find_distress_beacon(input, max as isize)?.into()),
+        reference: |input, max| {
+            Ok(day15::reference::find_distress_beacon(input, max as isize)?.into())
+        },
+    },
+    Check {
+        day: 16,
+        name: "part 1",
+        param: None,
+        cases: || day16::gen::input().prop_map(|input| (input, 0)).boxed(),
+        fast: |input, _| day16::solve_part1(input),
+        reference: |input, _| Ok(day16::reference::part_1(input)?.into()),
+    },
+    Check {
+        day: 16,
+        name: "part 2",
+        param: None,
+        cases: || day16::gen::input().prop_map(|input| (input, 0)).boxed(),
+        fast: |input, _| day16::solve_part2(input),
+        reference: |input, _| Ok(day16::reference::part_2(input)?.into()),
+    },
+    Check {
+        day: 17,
+        name: "tower height",
+        param: Some("rocks"),
+        cases: || (day17::gen::input(), 10_000..50_000usize).boxed(),
+        fast: |input, rocks| Ok(day17::tower_height(input, rocks)?.into()),
+        reference: |input, rocks| Ok(day17::reference::tower_height(input, rocks)?.into()),
+    },
+];
+
+fn runner(seed: u64) -> TestRunner {
+    let mut bytes = [0; 32];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+
+    TestRunner::new_with_rng(
+        Config::default(),
+        TestRng::from_seed(RngAlgorithm::ChaCha, &bytes),
+    )
+}
+
+fn show(result: &Result<Answer, AnyhowError>) -> String {
+    match result {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+/// Runs `cases` random cases through a check, stopping at the first one the
+/// solutions disagree on.
+fn run_check(check: &Check, cases: u32, seed: u64) -> Result<(), AnyhowError> {
+    let mut runner = runner(seed);
+    let strategy = (check.cases)();
+
+    for i in 1..=cases {
+        let (input, param) = strategy
+            .new_tree(&mut runner)
+            .map_err(|e| anyhow!("Can't generate a case: {}", e))?
+            .current();
+
+        let reference = (check.reference)(&input, param);
+        if let Err(e) = &reference {
+            bail!("Reference solution failed on case {}: {}\n{}", i, e, input);
+        }
+
+        let fast = (check.fast)(&input, param);
+        if fast.as_ref().ok() != reference.as_ref().ok() {
+            println!(
+                "day {}, {}: mismatch on case {} of seed {}",
+                check.day, check.name, i, seed
+            );
+            if let Some(name) = check.param {
+                println!("{}: {}", name, param);
+            }
+            println!("fast:      {}", show(&fast));
+            println!("reference: {}", show(&reference));
+            println!("input:\n{}", input);
+
+            bail!(
+                "Day {} {} disagrees with its reference",
+                check.day,
+                check.name
+            );
+        }
+    }
+
+    println!("day {}, {}: {} cases agree", check.day, check.name, cases);
+
+    Ok(())
+}
+
+pub fn diff(args: DiffArgs) -> Result<(), AnyhowError> {
+    let checks = CHECKS
+        .iter()
+        .filter(|c| args.day.is_none_or(|day| c.day == day))
+        .collect::<Vec<_>>();
+    if checks.is_empty() {
+        bail!("Day {} has no reference solver", args.day.unwrap_or(0));
+    }
+
+    let seed = args.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+    });
+    println!("seed {}", seed);
+
+    for check in checks {
+        run_check(check, args.cases, seed)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_runs_repeat() {
+        for check in CHECKS {
+            let case = |seed| {
+                (check.cases)()
+                    .new_tree(&mut runner(seed))
+                    .unwrap()
+                    .current()
+            };
+            assert_eq!(case(7), case(7));
+        }
+    }
+
+    #[test]
+    fn fast_solvers_agree_with_references() -> Result<(), AnyhowError> {
+        for check in CHECKS {
+            // Day 17's reference drops every rock of tens of thousands
+            let cases = if check.day == 17 { 2 } else { 20 };
+            run_check(check, cases, 3)?;
+        }
+
+        Ok(())
+    }
+}
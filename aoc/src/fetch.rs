@@ -0,0 +1,54 @@
+use crate::days;
+use anyhow::Error as AnyhowError;
+use aoc_common::fetch::{Cache, Fetcher, FixtureFetcher, HttpFetcher};
+use clap::Args;
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    /// Day to fetch the input of
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Fetch again even if the input is already cached
+    #[arg(long)]
+    force: bool,
+
+    /// Copy `dayN.txt` from this directory instead of downloading
+    #[arg(long, value_name = "DIR")]
+    fixtures: Option<PathBuf>,
+
+    /// Cache directory; defaults to $AOC_CACHE_DIR or the user cache
+    #[arg(long, value_name = "DIR")]
+    cache: Option<PathBuf>,
+}
+
+pub fn fetch(args: FetchArgs) -> Result<(), AnyhowError> {
+    let cache = match args.cache {
+        Some(dir) => Cache::new(dir),
+        None => Cache::user()?,
+    };
+
+    if cache.contains(args.day) && !args.force {
+        println!(
+            "Day {} is already cached in {}",
+            args.day,
+            cache.path(args.day).display()
+        );
+        return Ok(());
+    }
+
+    let fetcher: Box<dyn Fetcher> = match args.fixtures {
+        Some(dir) => Box::new(FixtureFetcher::new(dir)),
+        None => Box::new(HttpFetcher::new(cache.session()?)),
+    };
+
+    let path = cache.store(args.day, fetcher.as_ref())?;
+    println!("Fetched day {} input into {}", args.day, path.display());
+
+    if days::find(args.day).is_none() {
+        eprintln!("Note: day {} has no solver yet", args.day);
+    }
+
+    Ok(())
+}
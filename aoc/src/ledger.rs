@@ -0,0 +1,202 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::read_input;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, fs, path::Path};
+
+/// Default ledger file, relative to the workspace root.
+pub const DEFAULT_LEDGER: &str = "aoc-ledger.toml";
+
+/// What the puzzle site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Accepted,
+    Rejected,
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Accepted => write!(f, "accepted"),
+            Verdict::Rejected => write!(f, "rejected"),
+            Verdict::TooHigh => write!(f, "rejected, too high"),
+            Verdict::TooLow => write!(f, "rejected, too low"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Something off about a new answer, given what the ledger knows. Displays
+/// as the end of a sentence about the answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    AlreadyRejected(Verdict),
+    NotAccepted { accepted: String },
+    AboveBound { too_high: i64 },
+    BelowBound { too_low: i64 },
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::AlreadyRejected(verdict) => write!(f, "was already submitted and {}", verdict),
+            Warning::NotAccepted { accepted } => {
+                write!(f, "differs from the accepted answer {}", accepted)
+            }
+            Warning::AboveBound { too_high } => {
+                write!(f, "is not below {}, which was too high", too_high)
+            }
+            Warning::BelowBound { too_low } => {
+                write!(f, "is not above {}, which was too low", too_low)
+            }
+        }
+    }
+}
+
+/// Record of every answer submitted so far, kept as a TOML file of
+/// `[[answer]]` tables.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(default, rename = "answer")]
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`, empty if there is none yet.
+    pub fn load(path: &Path) -> Result<Self, AnyhowError> {
+        if !path.exists() {
+            return Ok(Ledger::default());
+        }
+
+        let text = read_input(path)?;
+        toml::from_str(&text).map_err(|e| anyhow!("Bad ledger {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnyhowError> {
+        let text = toml::to_string(self)?;
+        fs::write(path, text).map_err(|e| anyhow!("Can't write {}: {}", path.display(), e))
+    }
+
+    pub fn entries(&self, day: u8, part: u8) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |e| e.day == day && e.part == part)
+    }
+
+    pub fn accepted(&self, day: u8, part: u8) -> Option<&str> {
+        self.entries(day, part)
+            .find(|e| e.verdict == Verdict::Accepted)
+            .map(|e| e.answer.as_str())
+    }
+
+    /// Records the verdict on `answer`, replacing an earlier one.
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, verdict: Verdict) {
+        self.entries
+            .retain(|e| !(e.day == day && e.part == part && e.answer == answer));
+        self.entries.push(Entry {
+            day,
+            part,
+            answer: answer.to_owned(),
+            verdict,
+        });
+        self.entries.sort_by_key(|e| (e.day, e.part));
+    }
+
+    /// Checks a new answer against the recorded verdicts and bounds.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Vec<Warning> {
+        let mut warnings = vec![];
+
+        if let Some(accepted) = self.accepted(day, part) {
+            if accepted != answer {
+                warnings.push(Warning::NotAccepted {
+                    accepted: accepted.to_owned(),
+                });
+            }
+            return warnings;
+        }
+
+        if let Some(e) = self.entries(day, part).find(|e| e.answer == answer) {
+            warnings.push(Warning::AlreadyRejected(e.verdict));
+            return warnings;
+        }
+
+        let number = match answer.parse::<i64>() {
+            Ok(n) => n,
+            Err(_) => return warnings,
+        };
+        let bound = |verdict| {
+            self.entries(day, part)
+                .filter(move |e| e.verdict == verdict)
+                .filter_map(|e| e.answer.parse::<i64>().ok())
+        };
+
+        if let Some(too_high) = bound(Verdict::TooHigh).min().filter(|&b| number >= b) {
+            warnings.push(Warning::AboveBound { too_high });
+        }
+        if let Some(too_low) = bound(Verdict::TooLow).max().filter(|&b| number <= b) {
+            warnings.push(Warning::BelowBound { too_low });
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger() -> Ledger {
+        let mut ledger = Ledger::default();
+        ledger.record(1, 1, "100", Verdict::TooHigh);
+        ledger.record(1, 1, "10", Verdict::TooLow);
+        ledger.record(1, 1, "50", Verdict::Rejected);
+        ledger.record(1, 2, "7", Verdict::Accepted);
+        ledger
+    }
+
+    #[test]
+    fn warns_about_rejected_and_out_of_bound_answers() {
+        let ledger = ledger();
+
+        assert_eq!(ledger.check(1, 1, "42"), vec![]);
+        assert_eq!(
+            ledger.check(1, 1, "50"),
+            vec![Warning::AlreadyRejected(Verdict::Rejected)]
+        );
+        assert_eq!(
+            ledger.check(1, 1, "120"),
+            vec![Warning::AboveBound { too_high: 100 }]
+        );
+        assert_eq!(
+            ledger.check(1, 1, "3"),
+            vec![Warning::BelowBound { too_low: 10 }]
+        );
+        assert_eq!(ledger.check(1, 2, "7"), vec![]);
+        assert_eq!(
+            ledger.check(1, 2, "8"),
+            vec![Warning::NotAccepted {
+                accepted: "7".to_owned()
+            }]
+        );
+        assert_eq!(ledger.check(2, 1, "1"), vec![]);
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let ledger = ledger();
+
+        let text = toml::to_string(&ledger).unwrap();
+        assert!(text
+            .contains("[[answer]]\nday = 1\npart = 1\nanswer = \"100\"\nverdict = \"too-high\"\n"));
+        assert_eq!(toml::from_str::<Ledger>(&text).unwrap(), ledger);
+    }
+}
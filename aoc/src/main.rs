@@ -0,0 +1,55 @@
+mod bench;
+mod days;
+#[cfg(feature = "reference")]
+mod diff;
+mod fetch;
+mod ledger;
+mod new;
+mod run;
+mod submit;
+mod watch;
+
+use anyhow::Error as AnyhowError;
+use clap::{Parser, Subcommand};
+
+/// Advent of Code 2022 solutions runner
+#[derive(Debug, Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run a day's solvers and print the answers
+    Run(run::RunArgs),
+    /// Time each day's parsing and parts, comparing with a saved baseline
+    Bench(bench::BenchArgs),
+    /// Download a day's puzzle input into the local cache
+    Fetch(fetch::FetchArgs),
+    /// Submit an answer and record the verdict in the answer ledger
+    Submit(submit::SubmitArgs),
+    /// Create and register the crate of a new day
+    New(new::NewArgs),
+    /// Rebuild and rerun a day's example tests and solvers on every change
+    Watch(watch::WatchArgs),
+    /// Compare fast solvers with brute-force references on random inputs
+    #[cfg(feature = "reference")]
+    Diff(diff::DiffArgs),
+}
+
+fn main() -> Result<(), AnyhowError> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run(args) => run::run(args),
+        Command::Bench(args) => bench::bench(args),
+        Command::Fetch(args) => fetch::fetch(args),
+        Command::Submit(args) => submit::submit(args),
+        Command::New(args) => new::new(args),
+        Command::Watch(args) => watch::watch(args),
+        #[cfg(feature = "reference")]
+        Command::Diff(args) => diff::diff(args),
+    }
+}
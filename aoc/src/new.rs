@@ -0,0 +1,223 @@
+use crate::days;
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{fetch, read_input};
+use clap::Args;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// Day to create the crate of
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Workspace root
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+}
+
+/// Files of a new day crate, relative to its directory.
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    (
+        "benches/{{name}}.rs",
+        include_str!("../templates/bench.rs.tmpl"),
+    ),
+    ("input_simple.txt", ""),
+];
+
+fn fill(template: &str, day: u8) -> String {
+    template
+        .replace("{{name}}", &format!("day{}", day))
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Inserts `line` among the lines for which `number` gives a day number,
+/// keeping them sorted.
+fn insert_sorted(
+    text: &str,
+    number: impl Fn(&str) -> Option<u8>,
+    day: u8,
+    line: &str,
+) -> Result<String, AnyhowError> {
+    let lines = text.lines().collect::<Vec<_>>();
+
+    let numbered = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| number(l.trim()).map(|n| (i, n)))
+        .collect::<Vec<_>>();
+
+    if numbered.iter().any(|&(_, n)| n == day) {
+        bail!("Day {} is already registered", day);
+    }
+
+    let at = match numbered.iter().find(|&&(_, n)| n > day) {
+        Some(&(i, _)) => i,
+        None => {
+            numbered
+                .last()
+                .ok_or(anyhow!("No day entries to add to"))?
+                .0
+                + 1
+        }
+    };
+
+    let mut lines = lines;
+    lines.insert(at, line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn day_number(s: &str, prefix: &str, suffix: &str) -> Option<u8> {
+    s.strip_prefix(prefix)?.strip_suffix(suffix)?.parse().ok()
+}
+
+/// Edits `path` in place with `edit`.
+fn register(
+    path: &Path,
+    edit: impl Fn(&str) -> Result<String, AnyhowError>,
+) -> Result<(), AnyhowError> {
+    let text = read_input(path)?;
+    let text = edit(&text).map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+    fs::write(path, text).map_err(|e| anyhow!("Can't write {}: {}", path.display(), e))
+}
+
+fn register_member(manifest: &str, day: u8) -> Result<String, AnyhowError> {
+    insert_sorted(
+        manifest,
+        |l| day_number(l, "\"day", "\","),
+        day,
+        &format!("    \"day{}\",", day),
+    )
+}
+
+fn register_dependency(manifest: &str, day: u8) -> Result<String, AnyhowError> {
+    insert_sorted(
+        manifest,
+        |l| {
+            let (name, _) = l.split_once(" = { path = ")?;
+            day_number(name, "day", "")
+        },
+        day,
+        &format!("day{} = {{ path = \"../day{}\" }}", day, day),
+    )
+}
+
+fn register_day(days_rs: &str, day: u8) -> Result<String, AnyhowError> {
+    insert_sorted(
+        days_rs,
+        |l| {
+            let (number, _) = l.strip_prefix("day!(")?.split_once(',')?;
+            number.parse().ok()
+        },
+        day,
+        &format!("    day!({}, day{}),", day, day),
+    )
+}
+
+/// Puts the puzzle input in place for the benchmark, if it can be had.
+fn copy_input(day: u8, dir: &Path) -> Result<(), AnyhowError> {
+    let local = dir.join("input.txt");
+
+    match fetch::default_input(day, &local) {
+        Ok(cached) => {
+            fs::copy(&cached, &local)
+                .map_err(|e| anyhow!("Can't write {}: {}", local.display(), e))?;
+        }
+        Err(e) => {
+            eprintln!("No input yet ({}), put it in {}", e, local.display());
+            fs::write(&local, "").map_err(|e| anyhow!("Can't write {}: {}", local.display(), e))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn new(args: NewArgs) -> Result<(), AnyhowError> {
+    let day = args.day;
+    let name = format!("day{}", day);
+    let dir = args.root.join(&name);
+
+    let manifest = args.root.join("Cargo.toml");
+    if !read_input(&manifest)?.contains("[workspace]") {
+        bail!("{} is not the workspace manifest", manifest.display());
+    }
+    if days::find(day).is_some() || dir.exists() {
+        bail!("Day {} already exists", day);
+    }
+
+    for (path, template) in TEMPLATES {
+        let path = dir.join(fill(path, day));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| anyhow!("Can't create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, fill(template, day))
+            .map_err(|e| anyhow!("Can't write {}: {}", path.display(), e))?;
+    }
+    copy_input(day, &dir)?;
+
+    register(&manifest, |text| register_member(text, day))?;
+    register(&args.root.join("aoc/Cargo.toml"), |text| {
+        register_dependency(text, day)
+    })?;
+    register(&args.root.join("aoc/src/days.rs"), |text| {
+        register_day(text, day)
+    })?;
+
+    println!("Created {}", dir.display());
+    println!(
+        "Next: put the example in {}/input_simple.txt and its answers in the example tests",
+        name
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_members_in_order() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day3\",\n]\n";
+
+        assert_eq!(
+            register_member(manifest, 2).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n    \"day3\",\n]\n"
+        );
+        assert_eq!(
+            register_member(manifest, 4).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day3\",\n    \"day4\",\n]\n"
+        );
+        assert!(register_member(manifest, 3).is_err());
+    }
+
+    #[test]
+    fn registers_dependencies_and_days() {
+        let manifest = "[dependencies]\nclap = \"4\"\nday9 = { path = \"../day9\" }\nday10 = { path = \"../day10\" }\n";
+        assert_eq!(
+            register_dependency(manifest, 19).unwrap(),
+            manifest.to_owned() + "day19 = { path = \"../day19\" }\n"
+        );
+
+        let days_rs = "pub static DAYS: &[Day] = &[\n    day!(1, day1),\n];\n";
+        assert_eq!(
+            register_day(days_rs, 2).unwrap(),
+            "pub static DAYS: &[Day] = &[\n    day!(1, day1),\n    day!(2, day2),\n];\n"
+        );
+    }
+
+    #[test]
+    fn templates_are_filled_in() {
+        let main = fill(TEMPLATES[2].1, 19);
+
+        assert!(main.contains("aoc_common::day_main(19, day19::solve_part1, day19::solve_part2)"));
+        assert!(!TEMPLATES.iter().any(|(_, t)| fill(t, 19).contains("{{")));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    days::{self, Day},
+    ledger::{Ledger, DEFAULT_LEDGER},
+};
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{debug, fetch, parse, read_input, visual, Answer};
+use clap::{ArgAction, Args, ValueEnum};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Day to run
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Part to run; both parts are run when omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Input file, `-` for stdin; defaults to `dayN/input.txt`, or the cached
+    /// input when there is none
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+
+    /// Run every implemented day in sequence
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+
+    /// Fail on malformed input lines instead of skipping them
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    strict: bool,
+
+    /// Print the solvers' diagnostics on stderr
+    #[arg(long)]
+    debug: bool,
+
+    /// Output format of the answers; diagnostics always go to stderr
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Answer ledger new answers are checked against
+    #[arg(long, default_value = DEFAULT_LEDGER)]
+    ledger: PathBuf,
+
+    /// Write PNG/GIF renderings of the simulations to this directory
+    #[arg(long, value_name = "DIR")]
+    visualize: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable lines
+    Text,
+    /// One `{day, part, answer, elapsed_ms}` JSON record per line
+    Json,
+}
+
+/// Input of `day` when none is given: `dayN/input.txt` if present, the
+/// cached input otherwise.
+pub fn default_input(day: &Day) -> Result<PathBuf, AnyhowError> {
+    let local = PathBuf::from(format!("day{}/input.txt", day.number));
+    fetch::default_input(day.number, &local)
+}
+
+/// A line of `--format json` output.
+#[derive(Debug, Serialize)]
+struct Record {
+    day: u8,
+    part: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elapsed_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Record {
+    fn print(&self) {
+        println!(
+            "{}",
+            serde_json::to_string(self).expect("records serialize")
+        );
+    }
+}
+
+fn print_answer(day: &Day, part: u8, answer: &Answer, elapsed: Duration, format: Format) {
+    if format == Format::Json {
+        let answer = match answer {
+            // JSON numbers hold any 64-bit answer; larger ones go as text
+            Answer::Number(n) => i64::try_from(*n)
+                .map(Value::from)
+                .or_else(|_| u64::try_from(*n).map(Value::from))
+                .unwrap_or_else(|_| Value::from(n.to_string())),
+            Answer::Text(s) => Value::from(s.as_str()),
+        };
+        Record {
+            day: day.number,
+            part,
+            answer: Some(answer),
+            elapsed_ms: Some(elapsed.as_secs_f64() * 1000.0),
+            error: None,
+        }
+        .print();
+        return;
+    }
+
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("Day {}, part {} ({:.3?}):", day.number, part, elapsed);
+        println!("{}", answer);
+    } else {
+        println!(
+            "Day {}, part {}: {} ({:.3?})",
+            day.number, part, answer, elapsed
+        );
+    }
+}
+
+fn print_failure(day: &Day, part: u8, e: &AnyhowError, format: Format) {
+    match format {
+        Format::Text => println!("Day {}, part {}: failed: {}", day.number, part, e),
+        Format::Json => Record {
+            day: day.number,
+            part,
+            answer: None,
+            elapsed_ms: None,
+            error: Some(e.to_string()),
+        }
+        .print(),
+    }
+}
+
+/// Warns on stderr about answers the ledger knows to be wrong.
+fn check_answer(ledger: &Ledger, day: &Day, part: u8, answer: &Answer) {
+    let answer = answer.to_string();
+    for w in ledger.check(day.number, part, &answer) {
+        eprintln!(
+            "Warning: day {}, part {}: {} {}",
+            day.number, part, answer, w
+        );
+    }
+}
+
+fn run_day(
+    day: &Day,
+    parts: &[u8],
+    input: &Path,
+    ledger: &Ledger,
+    format: Format,
+) -> Result<usize, AnyhowError> {
+    let path = input.display().to_string();
+    let input = read_input(input)?;
+
+    let mut failed = 0;
+    for &part in parts {
+        let started = Instant::now();
+        match (day.solver(part))(&input) {
+            Ok(answer) => {
+                print_answer(day, part, &answer, started.elapsed(), format);
+                check_answer(ledger, day, part, &answer);
+            }
+            Err(e) => {
+                let e = parse::attach_file(e, &path);
+                print_failure(day, part, &e, format);
+                failed += 1;
+            }
+        }
+    }
+
+    Ok(failed)
+}
+
+pub fn run(args: RunArgs) -> Result<(), AnyhowError> {
+    parse::set_strict(args.strict);
+    debug::set_enabled(args.debug);
+    visual::set_output(args.visualize.clone());
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let selected: Vec<&Day> = if args.all {
+        days::DAYS.iter().collect()
+    } else {
+        let number = args.day.ok_or(anyhow!("No day given"))?;
+        vec![days::find(number).ok_or(anyhow!("Day {} is not implemented", number))?]
+    };
+
+    let ledger = Ledger::load(&args.ledger)?;
+
+    let started = Instant::now();
+    let mut failed = 0;
+    for day in &selected {
+        let input = match &args.input {
+            Some(input) => input.clone(),
+            None => default_input(day)?,
+        };
+        failed += run_day(day, &parts, &input, &ledger, args.format)?;
+    }
+
+    if args.all && args.format == Format::Text {
+        println!("Total: {:.3?}", started.elapsed());
+    }
+
+    if failed > 0 {
+        bail!("{} part(s) failed", failed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_skip_missing_fields() {
+        let record = Record {
+            day: 5,
+            part: 1,
+            answer: Some(Value::from("CMZ")),
+            elapsed_ms: Some(1.5),
+            error: None,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"day":5,"part":1,"answer":"CMZ","elapsed_ms":1.5}"#
+        );
+    }
+}
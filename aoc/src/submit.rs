@@ -0,0 +1,284 @@
+use crate::{
+    days,
+    ledger::{Ledger, Verdict, DEFAULT_LEDGER},
+    run::default_input,
+};
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{
+    fetch::{Cache, BASE_URL, YEAR},
+    golden::{self, ANSWERS_FILE},
+    parse, read_input,
+};
+use clap::Args;
+use std::{collections::HashMap, path::PathBuf};
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    /// Day to submit the answer of
+    #[arg(long)]
+    day: u8,
+
+    /// Part to submit the answer of
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Answer to submit; solved from the input when omitted
+    #[arg(long)]
+    answer: Option<String>,
+
+    /// Input file used to solve the part; defaults as for `aoc run`
+    #[arg(long, conflicts_with = "answer")]
+    input: Option<PathBuf>,
+
+    /// Answer ledger to check against and record the verdict in
+    #[arg(long, default_value = DEFAULT_LEDGER)]
+    ledger: PathBuf,
+
+    /// Judge against the local `dayN/answers.txt` files instead of the site
+    #[arg(long)]
+    mock: bool,
+
+    /// Submit even if the ledger says the answer can't be right
+    #[arg(long)]
+    force: bool,
+
+    /// Site to submit to, e.g. a local stub
+    #[arg(long, default_value = BASE_URL)]
+    base_url: String,
+}
+
+/// Judge of submitted answers.
+pub trait Submitter {
+    fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, AnyhowError>;
+}
+
+/// Posts answers to the Advent of Code site with a session token.
+pub struct HttpSubmitter {
+    session: String,
+    base_url: String,
+}
+
+impl HttpSubmitter {
+    pub fn new(session: impl Into<String>) -> Self {
+        HttpSubmitter {
+            session: session.into(),
+            base_url: BASE_URL.to_owned(),
+        }
+    }
+
+    /// Talks to another server than adventofcode.com, e.g. a local stub.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+}
+
+/// Reads the verdict out of the page the site answers a submission with.
+fn verdict_from_page(page: &str) -> Result<Verdict, AnyhowError> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Accepted)
+    } else if page.contains("your answer is too high") {
+        Ok(Verdict::TooHigh)
+    } else if page.contains("your answer is too low") {
+        Ok(Verdict::TooLow)
+    } else if page.contains("That's not the right answer") {
+        Ok(Verdict::Rejected)
+    } else if page.contains("You gave an answer too recently") {
+        bail!("Answer submitted too recently, wait a bit before trying again")
+    } else if page.contains("You don't seem to be solving the right level") {
+        bail!("This part is either locked or already solved")
+    } else {
+        bail!("Unexpected response to the submission")
+    }
+}
+
+impl Submitter for HttpSubmitter {
+    fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, AnyhowError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+
+        let page = ureq::post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "aoc2022 answer submitter (aoc submit)")
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| anyhow!("Can't submit the answer of day {}: {}", day, e))?
+            .into_string()?;
+
+        verdict_from_page(&page)
+    }
+}
+
+/// Judges answers against known correct ones, like the site would.
+pub struct MockSubmitter {
+    correct: HashMap<(u8, u8), String>,
+}
+
+impl MockSubmitter {
+    pub fn new(correct: HashMap<(u8, u8), String>) -> Self {
+        MockSubmitter { correct }
+    }
+
+    /// Uses the answers recorded in each day's answers file.
+    pub fn from_answer_files() -> Result<Self, AnyhowError> {
+        let mut correct = HashMap::new();
+
+        for day in days::DAYS {
+            let answers = read_input(format!("day{}/{}", day.number, ANSWERS_FILE))?;
+            for part in [1, 2] {
+                if let Some(answer) = golden::find_answer(&answers, part) {
+                    correct.insert((day.number, part), answer);
+                }
+            }
+        }
+
+        Ok(MockSubmitter::new(correct))
+    }
+}
+
+impl Submitter for MockSubmitter {
+    fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, AnyhowError> {
+        let correct = self.correct.get(&(day, part)).ok_or(anyhow!(
+            "No known answer for day {}, part {}",
+            day,
+            part
+        ))?;
+
+        let verdict = match (answer.parse::<i64>(), correct.parse::<i64>()) {
+            _ if answer == correct => Verdict::Accepted,
+            (Ok(a), Ok(c)) if a > c => Verdict::TooHigh,
+            (Ok(a), Ok(c)) if a < c => Verdict::TooLow,
+            _ => Verdict::Rejected,
+        };
+
+        Ok(verdict)
+    }
+}
+
+fn solve(day: u8, part: u8, input: Option<PathBuf>) -> Result<String, AnyhowError> {
+    let day = days::find(day).ok_or(anyhow!("Day {} is not implemented", day))?;
+    let path = match input {
+        Some(input) => input,
+        None => default_input(day)?,
+    };
+
+    let answer = (day.solver(part))(&read_input(&path)?)
+        .map_err(|e| parse::attach_file(e, &path.display().to_string()))?;
+
+    Ok(answer.to_string())
+}
+
+pub fn submit(args: SubmitArgs) -> Result<(), AnyhowError> {
+    let mut ledger = Ledger::load(&args.ledger)?;
+
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => solve(args.day, args.part, args.input)?,
+    };
+    if answer.contains('\n') {
+        bail!("Multiline answer, read it off and pass it with --answer");
+    }
+
+    if let Some(accepted) = ledger.accepted(args.day, args.part) {
+        if accepted == answer {
+            println!(
+                "Day {}, part {}: {} was already accepted",
+                args.day, args.part, answer
+            );
+            return Ok(());
+        }
+    }
+
+    let warnings = ledger.check(args.day, args.part, &answer);
+    for w in &warnings {
+        eprintln!(
+            "Warning: day {}, part {}: {} {}",
+            args.day, args.part, answer, w
+        );
+    }
+    if !warnings.is_empty() && !args.force {
+        bail!("Not submitting {}, pass --force to submit anyway", answer);
+    }
+
+    let submitter: Box<dyn Submitter> = if args.mock {
+        Box::new(MockSubmitter::from_answer_files()?)
+    } else {
+        Box::new(HttpSubmitter::new(Cache::user()?.session()?).with_base_url(args.base_url))
+    };
+
+    let verdict = submitter.submit(args.day, args.part, &answer)?;
+    ledger.record(args.day, args.part, &answer, verdict);
+    ledger.save(&args.ledger)?;
+
+    println!(
+        "Day {}, part {}: {} {}",
+        args.day, args.part, answer, verdict
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::test_util::stub_server;
+
+    fn page(text: &str) -> String {
+        format!(
+            "<html><main><article><p>{}</p></article></main></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn reads_verdicts_off_the_response_page() {
+        assert_eq!(
+            verdict_from_page(&page(
+                "That's the right answer! You are one gold star closer."
+            ))
+            .unwrap(),
+            Verdict::Accepted
+        );
+        assert_eq!(
+            verdict_from_page(&page(
+                "That's not the right answer; your answer is too high."
+            ))
+            .unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            verdict_from_page(&page("That's not the right answer.")).unwrap(),
+            Verdict::Rejected
+        );
+        assert!(verdict_from_page(&page("You gave an answer too recently.")).is_err());
+    }
+
+    #[test]
+    fn mock_judges_like_the_site() {
+        let mock = MockSubmitter::new(HashMap::from([
+            ((1, 1), "42".to_owned()),
+            ((5, 1), "CMZ".to_owned()),
+        ]));
+
+        assert_eq!(mock.submit(1, 1, "42").unwrap(), Verdict::Accepted);
+        assert_eq!(mock.submit(1, 1, "50").unwrap(), Verdict::TooHigh);
+        assert_eq!(mock.submit(1, 1, "7").unwrap(), Verdict::TooLow);
+        assert_eq!(mock.submit(5, 1, "MCD").unwrap(), Verdict::Rejected);
+        assert!(mock.submit(1, 2, "1").is_err());
+    }
+
+    #[test]
+    fn http_submitter_posts_the_answer() -> Result<(), AnyhowError> {
+        let (url, server) = stub_server("200 OK", page("That's the right answer!"));
+
+        let verdict = HttpSubmitter::new("s3cr3t")
+            .with_base_url(url)
+            .submit(6, 2, "2308")?;
+        assert_eq!(verdict, Verdict::Accepted);
+
+        let (head, body) = server.join().unwrap();
+        assert!(head.starts_with("POST /2022/day/6/answer "));
+        assert!(head.contains("session=s3cr3t"));
+        assert_eq!(body, "level=2&answer=2308");
+
+        Ok(())
+    }
+}
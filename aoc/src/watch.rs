@@ -0,0 +1,246 @@
+use crate::{
+    days::{self, Day},
+    run::default_input,
+};
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::golden;
+use clap::Args;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::{
+    collections::BTreeMap,
+    env,
+    path::Path,
+    process::{Command, Output},
+    sync::mpsc,
+    time::Duration,
+};
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// Day to watch
+    #[arg(long)]
+    day: u8,
+
+    /// Build without optimizations: quicker rebuilds, slower solvers
+    #[arg(long)]
+    debug: bool,
+}
+
+/// Answer of a part on the real input, or why there is none.
+type PartResult = Result<String, String>;
+
+/// How long to wait for more changes before rebuilding.
+const SETTLE: Duration = Duration::from_millis(200);
+
+fn cargo(args: &[&str], debug: bool) -> Result<Output, AnyhowError> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+
+    let mut command = Command::new(cargo);
+    command.args(&args[..1]).arg("-q");
+    if !debug {
+        command.arg("--release");
+    }
+
+    command
+        .args(&args[1..])
+        .output()
+        .map_err(|e| anyhow!("Can't run cargo: {}", e))
+}
+
+/// Names of the failed tests in `cargo test` output.
+fn failed_tests(stdout: &str) -> Vec<&str> {
+    stdout
+        .lines()
+        .filter_map(|l| l.strip_prefix("---- ")?.strip_suffix(" stdout ----"))
+        .collect()
+}
+
+/// First lines of the compiler errors in `cargo` output.
+fn build_errors(stderr: &str) -> Vec<&str> {
+    stderr
+        .lines()
+        .filter(|l| l.starts_with("error"))
+        .take(5)
+        .collect()
+}
+
+/// Runs the example tests, returning whether the build succeeded.
+fn run_examples(day: u8, debug: bool) -> Result<bool, AnyhowError> {
+    let package = format!("day{}", day);
+    let output = cargo(&["test", "-p", &package, "example"], debug)?;
+
+    if output.status.success() {
+        println!("examples: pass");
+        return Ok(true);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let failed = failed_tests(&stdout);
+    if !failed.is_empty() {
+        println!("examples: FAIL {}", failed.join(", "));
+        return Ok(true);
+    }
+
+    println!("build: FAIL");
+    for line in build_errors(&String::from_utf8_lossy(&output.stderr)) {
+        println!("  {}", line);
+    }
+
+    Ok(false)
+}
+
+/// Solves both parts on the real input with the day binary, whose output
+/// has the layout of an answers file.
+fn run_input(day: &Day, debug: bool) -> Result<BTreeMap<u8, PartResult>, AnyhowError> {
+    let input = default_input(day)?;
+    let output = cargo(
+        &[
+            "run",
+            "-p",
+            &format!("day{}", day.number),
+            "--",
+            &input.display().to_string(),
+        ],
+        debug,
+    )?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error = stderr
+        .lines()
+        .rev()
+        .find_map(|l| l.strip_prefix("Error: "))
+        .unwrap_or("no answer");
+
+    Ok([1, 2]
+        .into_iter()
+        .map(|part| {
+            let result = golden::find_answer(&stdout, part).ok_or_else(|| error.to_owned());
+            (part, result)
+        })
+        .collect())
+}
+
+/// One line about a part's result and how it differs from the last run.
+fn describe(part: u8, new: &PartResult, old: Option<&PartResult>) -> String {
+    let now = match new {
+        Ok(answer) if answer.contains('\n') => format!("\n{}\n", answer),
+        Ok(answer) => answer.clone(),
+        Err(e) => format!("failed: {}", e),
+    };
+
+    let change = match old {
+        None => String::new(),
+        Some(old) if old == new => " (unchanged)".to_owned(),
+        Some(Ok(answer)) if !answer.contains('\n') => format!(" (was {})", answer),
+        Some(Ok(_)) => " (changed)".to_owned(),
+        Some(Err(_)) => " (was failing)".to_owned(),
+    };
+
+    format!("part {}: {}{}", part, now, change)
+}
+
+fn cycle(day: &Day, debug: bool, last: &mut BTreeMap<u8, PartResult>) -> Result<(), AnyhowError> {
+    println!("---- day {}: rebuilding", day.number);
+
+    if !run_examples(day.number, debug)? {
+        return Ok(());
+    }
+
+    let results = run_input(day, debug)?;
+    for (&part, result) in &results {
+        println!("{}", describe(part, result, last.get(&part)));
+    }
+    *last = results;
+
+    Ok(())
+}
+
+/// Whether a file system event may change the build or the inputs.
+fn is_relevant(event: &Event) -> bool {
+    let source = event.paths.iter().any(|p| {
+        matches!(
+            p.extension().and_then(|e| e.to_str()),
+            Some("rs" | "txt" | "toml")
+        )
+    });
+
+    source
+        && matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        )
+}
+
+pub fn watch(args: WatchArgs) -> Result<(), AnyhowError> {
+    let day = days::find(args.day).ok_or(anyhow!("Day {} is not implemented", args.day))?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    for dir in [format!("day{}", args.day), "aoc-common/src".to_owned()] {
+        watcher
+            .watch(Path::new(&dir), RecursiveMode::Recursive)
+            .map_err(|e| anyhow!("Can't watch {}: {}", dir, e))?;
+    }
+
+    let mut last = BTreeMap::new();
+    loop {
+        // Whatever fails, e.g. a missing input, the next change may fix
+        if let Err(e) = cycle(day, args.debug, &mut last) {
+            eprintln!("Error: {}", e);
+        }
+
+        loop {
+            match rx.recv()? {
+                Ok(event) if is_relevant(&event) => break,
+                Ok(_) => {}
+                Err(e) => eprintln!("Error: {}", e),
+            }
+        }
+
+        while rx.recv_timeout(SETTLE).is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_answer_changes() {
+        let ok = |s: &str| Ok(s.to_owned());
+
+        assert_eq!(describe(1, &ok("42"), None), "part 1: 42");
+        assert_eq!(
+            describe(1, &ok("42"), Some(&ok("42"))),
+            "part 1: 42 (unchanged)"
+        );
+        assert_eq!(
+            describe(2, &ok("43"), Some(&ok("42"))),
+            "part 2: 43 (was 42)"
+        );
+        assert_eq!(
+            describe(2, &Err("No path found!".to_owned()), Some(&ok("42"))),
+            "part 2: failed: No path found! (was 42)"
+        );
+        assert_eq!(
+            describe(2, &ok("42"), Some(&Err("boom".to_owned()))),
+            "part 2: 42 (was failing)"
+        );
+    }
+
+    #[test]
+    fn finds_failed_tests_and_build_errors() {
+        let stdout = "\nrunning 2 tests\n.F\nfailures:\n\n---- tests::example_part2 stdout ----\nthread panicked\n";
+        assert_eq!(failed_tests(stdout), vec!["tests::example_part2"]);
+
+        let stderr = "error[E0425]: cannot find value `x` in this scope\n  --> day1/src/lib.rs:3:5\nerror: could not compile `day1`\n";
+        assert_eq!(
+            build_errors(stderr),
+            vec![
+                "error[E0425]: cannot find value `x` in this scope",
+                "error: could not compile `day1`"
+            ]
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+const INPUT: &str = include_str!("../input.txt");
+
+fn bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day1");
+    group.bench_function("parse", |b| b.iter(|| day1::parse(black_box(INPUT))));
+    group.bench_function("part1", |b| b.iter(|| day1::solve_part1(black_box(INPUT))));
+    group.bench_function("part2", |b| b.iter(|| day1::solve_part2(black_box(INPUT))));
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
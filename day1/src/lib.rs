@@ -0,0 +1,373 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::{
+    parse::{check, ParseError, ParseReport},
+    Answer, LineError,
+};
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+pub mod report;
+
+/// The items an elf carries, and the line its list starts on (1-based).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    pub line: usize,
+    pub items: Vec<u64>,
+}
+
+impl Elf {
+    /// Calories carried in total; the parser makes sure it fits.
+    pub fn total(&self) -> u64 {
+        self.items.iter().sum()
+    }
+}
+
+/// What a line of the calorie list holds.
+enum Line {
+    /// A blank line, ending the list of the elf with the given total, if any.
+    End(Option<u64>),
+    /// An item, `None` if malformed; `first` if it starts an elf's list.
+    Item { calories: Option<u64>, first: bool },
+}
+
+/// Checks the calorie list line by line, numbering the elves and keeping
+/// the total of the current one.
+#[derive(Default)]
+struct Tally {
+    /// Elves whose list has started so far.
+    elves: usize,
+    /// Total of the current elf, `None` between two lists.
+    total: Option<u64>,
+    errors: Vec<ParseError>,
+}
+
+impl Tally {
+    /// Reads line `i` (0-based). Lines holding only whitespace are blank,
+    /// and whitespace around an item is ignored.
+    fn read(&mut self, i: usize, line: &str) -> Line {
+        let s = line.trim();
+        if s.is_empty() {
+            return Line::End(self.total.take());
+        }
+
+        let first = self.total.is_none();
+        if first {
+            self.elves += 1;
+        }
+        let total = self.total.get_or_insert(0);
+
+        let item = match s.find(|c: char| !c.is_ascii_digit()) {
+            Some(bad) => Err(LineError::at(line, &s[bad..], "a calorie count")),
+            None => s
+                .parse::<u64>()
+                .ok()
+                .filter(|&v| total.checked_add(v).is_some())
+                .ok_or_else(|| {
+                    LineError::at(line, s, "a calorie count keeping the total within 64 bits")
+                }),
+        };
+
+        match item {
+            Ok(v) => {
+                *total += v;
+                Line::Item {
+                    calories: Some(v),
+                    first,
+                }
+            }
+            Err(e) => {
+                let e = LineError::new(e.column, format!("{} for elf {}", e.expected, self.elves));
+                self.errors.extend(ParseReport::single(i, line, e).errors);
+                Line::Item {
+                    calories: None,
+                    first,
+                }
+            }
+        }
+    }
+}
+
+/// Parses the calorie list into the items carried by each elf. An elf none
+/// of whose items are valid still counts, with no items.
+pub fn parse(input: &str) -> Result<Vec<Elf>, AnyhowError> {
+    let mut elves: Vec<Elf> = vec![];
+    let mut tally = Tally::default();
+
+    for (i, s) in input.lines().enumerate() {
+        if let Line::Item { calories, first } = tally.read(i, s) {
+            if first {
+                elves.push(Elf {
+                    line: i + 1,
+                    items: vec![],
+                });
+            }
+            if let (Some(elf), Some(v)) = (elves.last_mut(), calories) {
+                elf.items.push(v);
+            }
+        }
+    }
+
+    Ok(check(elves, tally.errors)?)
+}
+
+/// Largest calorie totals among the elves, in decreasing order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopElves {
+    pub k: usize,
+    pub totals: Vec<u64>,
+}
+
+impl TopElves {
+    /// Calories carried by the elf with the most.
+    pub fn part1(&self) -> Option<u64> {
+        self.totals.first().copied()
+    }
+
+    /// Calories carried by the three elves with the most, if `k` is at
+    /// least 3. Fails if they add up to more than 64 bits.
+    pub fn part2(&self) -> Result<Option<u64>, AnyhowError> {
+        if self.k < 3 {
+            return Ok(None);
+        }
+
+        self.totals
+            .iter()
+            .take(3)
+            .try_fold(0u64, |sum, &total| sum.checked_add(total))
+            .map(Some)
+            .ok_or_else(|| anyhow!("The three largest totals add up to more than 64 bits"))
+    }
+}
+
+/// Keeps the `k` largest totals seen so far in a min-heap, so that the
+/// smallest of them is the one to evict.
+fn push_total(heap: &mut BinaryHeap<Reverse<u64>>, k: usize, total: u64) {
+    if heap.len() < k {
+        heap.push(Reverse(total));
+    } else if heap.peek().is_some_and(|&Reverse(min)| total > min) {
+        heap.pop();
+        heap.push(Reverse(total));
+    }
+}
+
+/// Streams the calorie list line by line, keeping only the `k` largest elf
+/// totals: memory stays constant however many elves there are.
+pub fn top_elves(mut reader: impl BufRead, k: usize) -> Result<TopElves, AnyhowError> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    let mut tally = Tally::default();
+    let mut line = String::new();
+
+    for i in 0.. {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let s = line.strip_suffix('\n').unwrap_or(&line);
+        let s = s.strip_suffix('\r').unwrap_or(s);
+
+        if let Line::End(Some(total)) = tally.read(i, s) {
+            push_total(&mut heap, k, total);
+        }
+    }
+    if let Some(total) = tally.total {
+        push_total(&mut heap, k, total);
+    }
+
+    let totals = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(total)| total)
+        .collect();
+
+    Ok(check(TopElves { k, totals }, tally.errors)?)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(top_elves(input.as_bytes(), 1)?
+        .part1()
+        .ok_or(anyhow!("No elves!"))?
+        .into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(top_elves(input.as_bytes(), 3)?
+        .part2()?
+        .ok_or(anyhow!("No elves!"))?
+        .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use std::io::{self, BufReader, Read};
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(24000));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(45000));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    #[test]
+    fn top_elves_of_example() {
+        let top = top_elves(EXAMPLE.as_bytes(), 5).unwrap();
+        assert_eq!(top.totals, vec![24000, 11000, 10000, 6000, 4000]);
+        assert_eq!(top.part1(), Some(24000));
+        assert_eq!(top.part2().unwrap(), Some(45000));
+
+        let top = top_elves(EXAMPLE.as_bytes(), 10).unwrap();
+        assert_eq!(top.totals.len(), 5);
+
+        let top = top_elves(EXAMPLE.as_bytes(), 1).unwrap();
+        assert_eq!(top.part2().unwrap(), None);
+    }
+
+    fn errors(result: Result<impl std::fmt::Debug, AnyhowError>) -> Vec<ParseError> {
+        result
+            .unwrap_err()
+            .downcast::<ParseReport>()
+            .unwrap()
+            .errors
+    }
+
+    #[test]
+    fn blank_lines_may_hold_whitespace() {
+        let input = "1000\r\n2000\r\n \t\r\n3000\r\n\r\n  4000 \n";
+
+        let elves = parse(input).unwrap();
+        assert_eq!(
+            elves,
+            vec![
+                Elf {
+                    line: 1,
+                    items: vec![1000, 2000]
+                },
+                Elf {
+                    line: 4,
+                    items: vec![3000]
+                },
+                Elf {
+                    line: 6,
+                    items: vec![4000]
+                },
+            ]
+        );
+        assert_eq!(
+            top_elves(input.as_bytes(), 3).unwrap().totals,
+            [4000, 3000, 3000]
+        );
+    }
+
+    #[test]
+    fn reports_bad_lines_with_their_elf() {
+        let input = "1000\n\n2000\n30x0\n\n-5\n";
+
+        for errors in [errors(parse(input)), errors(top_elves(input.as_bytes(), 3))] {
+            assert_eq!(errors.len(), 2);
+            assert_eq!((errors[0].line, errors[0].column), (4, 3));
+            assert_eq!(errors[0].expected, "a calorie count for elf 2");
+            assert_eq!((errors[1].line, errors[1].column), (6, 1));
+            assert_eq!(errors[1].expected, "a calorie count for elf 3");
+        }
+    }
+
+    #[test]
+    fn totals_do_not_overflow() {
+        let big = format!("{}\n{}\n", u32::MAX, u32::MAX);
+        assert_eq!(
+            solve_part1(&big).unwrap(),
+            Answer::Number(2 * u32::MAX as i128)
+        );
+
+        let too_big = format!("1\n{}\n\n{}0\n", u64::MAX, u64::MAX);
+        for errors in [
+            errors(parse(&too_big)),
+            errors(top_elves(too_big.as_bytes(), 1)),
+        ] {
+            assert_eq!(errors.len(), 2);
+            assert_eq!(errors[0].line, 2);
+            assert_eq!(
+                errors[0].expected,
+                "a calorie count keeping the total within 64 bits for elf 1"
+            );
+            assert_eq!(errors[1].line, 4);
+        }
+    }
+
+    #[test]
+    fn answers_beyond_i64_do_not_wrap() {
+        let input = format!("{}\n1\n\n5\n", i64::MAX);
+        assert_eq!(
+            solve_part1(&input).unwrap().to_string(),
+            "9223372036854775808"
+        );
+
+        let input = format!("{}\n\n{}\n", u64::MAX, u64::MAX);
+        assert_eq!(
+            solve_part1(&input).unwrap(),
+            Answer::Number(u64::MAX as i128)
+        );
+        assert_eq!(
+            solve_part2(&input).unwrap_err().to_string(),
+            "The three largest totals add up to more than 64 bits"
+        );
+    }
+
+    /// Calorie list made up on the fly, elf `i` carrying `calories(i)`
+    /// over two items.
+    struct Inventory {
+        next: u64,
+        elves: u64,
+        pending: Vec<u8>,
+    }
+
+    fn calories(i: u64) -> u64 {
+        i * 7919 % 100_003 + 2
+    }
+
+    impl Read for Inventory {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() && self.next < self.elves {
+                let total = calories(self.next);
+                self.pending = format!("{}\n{}\n\n", total / 2, total - total / 2).into_bytes();
+                self.next += 1;
+            }
+
+            let n = buf.len().min(self.pending.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn streams_a_large_inventory() {
+        let elves = 300_000;
+        let inventory = Inventory {
+            next: 0,
+            elves,
+            pending: vec![],
+        };
+
+        let top = top_elves(BufReader::new(inventory), 4).unwrap();
+
+        let mut totals = (0..elves).map(calories).collect::<Vec<_>>();
+        totals.sort_by(|a, b| b.cmp(a));
+        assert_eq!(top.totals, totals[..4]);
+    }
+}
@@ -1,25 +1,64 @@
-use itertools::Itertools;
-use std::{error::Error, fs};
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let input = fs::read_to_string("./input.txt")?;
-
-    let sums: Vec<_> = input
-        .lines()
-        .group_by(|s| s.len() > 0)
-        .into_iter()
-        .map(|(_k, g)| g.filter_map(|s| s.parse::<u32>().ok()).collect::<Vec<_>>())
-        .filter(|v| !v.is_empty())
-        .map(|v| v.into_iter().sum::<u32>())
-        .collect();
-
-    println!(
-        "{:?}",
-        sums.into_iter()
-            .sorted_by(|a, b| Ord::cmp(b, a))
-            .take(3)
-            .sum::<u32>()
-    );
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{DayRun, Flag};
+use day1::report::{Format, Report};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+};
+
+/// `[--top K] [--report text|csv|json]`
+const FLAGS: [Flag; 2] = [Flag::value("top"), Flag::value("report")];
+
+/// Streams the input instead of reading it whole, printing the requested
+/// parts and, if given, the `top` largest totals.
+fn stream(day: &DayRun, top: Option<usize>) -> Result<(), AnyhowError> {
+    let reader: Box<dyn BufRead> = if day.path == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        let file = File::open(&day.path).map_err(|e| anyhow!("Can't read {}: {}", day.path, e))?;
+        Box::new(BufReader::new(file))
+    };
+
+    // At least the three largest, for part 2
+    let top_elves = day1::top_elves(reader, top.unwrap_or(0).max(3))?;
+
+    for &part in &day.parts {
+        let answer = if part == 1 {
+            top_elves.part1()
+        } else {
+            top_elves.part2()?
+        };
+        let answer = answer.ok_or(anyhow!("No elves!"))?;
+        println!("Part {}: {}", part, answer);
+    }
+
+    if let Some(k) = top {
+        let totals = top_elves.totals.iter().take(k).map(|t| t.to_string());
+        println!("Top {}: {}", k, totals.collect::<Vec<_>>().join(", "));
+    }
 
     Ok(())
 }
+
+/// Prints every elf's inventory, the `k` elves carrying the most and
+/// statistics over all of them, or else streams the input for the parts and
+/// the top `--top` elves.
+fn run(day: &DayRun) -> Result<(), AnyhowError> {
+    let top = match day.options.parse::<usize>("top")? {
+        Some(0) => bail!("Bad --top value 0"),
+        top => top,
+    };
+
+    match day.options.parse::<Format>("report")? {
+        Some(format) => {
+            let elves = day1::parse(&day.read_input()?)?;
+            print!("{}", Report::new(&elves, top.unwrap_or(3)).render(format)?);
+            Ok(())
+        }
+        None => stream(day, top),
+    }
+}
+
+fn main() -> Result<(), AnyhowError> {
+    aoc_common::day_main_handler(1, &FLAGS, run)
+}
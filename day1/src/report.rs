@@ -0,0 +1,330 @@
+//! Per-elf inventory report: every elf's items and total, the elves carrying
+//! the most, and statistics over all the totals.
+
+use crate::Elf;
+use anyhow::{bail, Error as AnyhowError};
+use serde::Serialize;
+use std::{cmp::Reverse, fmt::Write, str::FromStr};
+
+/// Percentiles listed in the statistics.
+const PERCENTILES: [u8; 6] = [10, 25, 50, 75, 90, 99];
+
+/// Number of buckets in the histogram of totals.
+const BUCKETS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ElfEntry {
+    /// 1-based position of the elf in the input.
+    pub elf: usize,
+    pub line: usize,
+    pub items: usize,
+    pub total: u64,
+    /// 1-based rank by total, ties going to the elf listed first.
+    pub rank: usize,
+}
+
+/// Totals from `from` to `to`, both included, and how many elves carry one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+    pub from: u64,
+    pub to: u64,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub elves: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// Nearest-rank percentiles, as `(percentile, total)`.
+    pub percentiles: Vec<(u8, u64)>,
+    pub histogram: Vec<Bucket>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub elves: Vec<ElfEntry>,
+    /// The `k` elves carrying the most, best first.
+    pub top: Vec<ElfEntry>,
+    pub stats: Option<Stats>,
+}
+
+/// Output format of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = AnyhowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            f => bail!("Unknown report format {}, expected text, csv or json", f),
+        }
+    }
+}
+
+fn stats(totals: &[u64]) -> Option<Stats> {
+    let mut sorted = totals.to_vec();
+    sorted.sort_unstable();
+
+    let n = sorted.len();
+    let (&min, &max) = (sorted.first()?, sorted.last()?);
+
+    let mean = sorted.iter().map(|&t| t as f64).sum::<f64>() / n as f64;
+    let median = if n % 2 == 1 {
+        sorted[n / 2] as f64
+    } else {
+        (sorted[n / 2 - 1] as u128 + sorted[n / 2] as u128) as f64 / 2.0
+    };
+
+    let percentiles = PERCENTILES
+        .iter()
+        .map(|&p| {
+            let rank = (p as usize * n).div_ceil(100).max(1);
+            (p, sorted[rank - 1])
+        })
+        .collect();
+
+    // Totals may span all of u64, so neither the width nor the bounds of the
+    // last buckets may overflow
+    let width = (max - min) / BUCKETS + 1;
+    let mut histogram = (0..BUCKETS)
+        .map_while(|b| {
+            let from = min.checked_add(b * width).filter(|&from| from <= max)?;
+            Some(Bucket {
+                from,
+                to: from.saturating_add(width - 1),
+                count: 0,
+            })
+        })
+        .collect::<Vec<_>>();
+    for t in sorted {
+        histogram[((t - min) / width) as usize].count += 1;
+    }
+
+    Some(Stats {
+        elves: n,
+        min,
+        max,
+        mean,
+        median,
+        percentiles,
+        histogram,
+    })
+}
+
+impl Report {
+    pub fn new(elves: &[Elf], k: usize) -> Self {
+        let mut entries = elves
+            .iter()
+            .enumerate()
+            .map(|(i, e)| ElfEntry {
+                elf: i + 1,
+                line: e.line,
+                items: e.items.len(),
+                total: e.total(),
+                rank: 0,
+            })
+            .collect::<Vec<_>>();
+
+        let mut by_total = (0..entries.len()).collect::<Vec<_>>();
+        by_total.sort_by_key(|&i| (Reverse(entries[i].total), i));
+        for (rank, &i) in by_total.iter().enumerate() {
+            entries[i].rank = rank + 1;
+        }
+
+        let top = by_total
+            .iter()
+            .take(k)
+            .map(|&i| entries[i].clone())
+            .collect();
+        let totals = entries.iter().map(|e| e.total).collect::<Vec<_>>();
+
+        Report {
+            elves: entries,
+            top,
+            stats: stats(&totals),
+        }
+    }
+
+    pub fn render(&self, format: Format) -> Result<String, AnyhowError> {
+        match format {
+            Format::Text => Ok(self.to_text()),
+            Format::Csv => Ok(self.to_csv()),
+            Format::Json => Ok(serde_json::to_string_pretty(self)? + "\n"),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(
+            out,
+            "{:>5} {:>6} {:>5} {:>8} {:>5}",
+            "elf", "line", "items", "total", "rank"
+        )
+        .unwrap();
+        for e in &self.elves {
+            writeln!(
+                out,
+                "{:>5} {:>6} {:>5} {:>8} {:>5}",
+                e.elf, e.line, e.items, e.total, e.rank
+            )
+            .unwrap();
+        }
+
+        writeln!(out, "\nTop {}:", self.top.len()).unwrap();
+        for e in &self.top {
+            writeln!(
+                out,
+                "{:>4}. elf {} (line {}): {}",
+                e.rank, e.elf, e.line, e.total
+            )
+            .unwrap();
+        }
+
+        if let Some(s) = &self.stats {
+            writeln!(
+                out,
+                "\n{} elves, totals from {} to {}",
+                s.elves, s.min, s.max
+            )
+            .unwrap();
+            writeln!(out, "mean {:.1}, median {:.1}", s.mean, s.median).unwrap();
+            let percentiles = s
+                .percentiles
+                .iter()
+                .map(|(p, t)| format!("p{} {}", p, t))
+                .collect::<Vec<_>>();
+            writeln!(out, "{}", percentiles.join(", ")).unwrap();
+
+            let most = s.histogram.iter().map(|b| b.count).max().unwrap_or(0);
+            for b in &s.histogram {
+                let bar = "#".repeat((b.count * 40).div_ceil(most.max(1)));
+                let line = format!("{:>8}-{:<8} {:>6} {}", b.from, b.to, b.count, bar);
+                writeln!(out, "{}", line.trim_end()).unwrap();
+            }
+        }
+
+        out
+    }
+
+    /// The elves as a CSV table, then the statistics as a second one,
+    /// separated by an empty line.
+    fn to_csv(&self) -> String {
+        let mut out = "elf,line,items,total,rank\n".to_owned();
+        for e in &self.elves {
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                e.elf, e.line, e.items, e.total, e.rank
+            )
+            .unwrap();
+        }
+
+        if let Some(s) = &self.stats {
+            out += "\nstatistic,value\n";
+            writeln!(out, "elves,{}", s.elves).unwrap();
+            writeln!(out, "min,{}", s.min).unwrap();
+            writeln!(out, "max,{}", s.max).unwrap();
+            writeln!(out, "mean,{}", s.mean).unwrap();
+            writeln!(out, "median,{}", s.median).unwrap();
+            for (p, t) in &s.percentiles {
+                writeln!(out, "p{},{}", p, t).unwrap();
+            }
+            for b in &s.histogram {
+                writeln!(out, "bucket {}-{},{}", b.from, b.to, b.count).unwrap();
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn ranks_the_example() {
+        let report = Report::new(&parse(EXAMPLE).unwrap(), 3);
+
+        assert_eq!(
+            report.elves[3],
+            ElfEntry {
+                elf: 4,
+                line: 10,
+                items: 3,
+                total: 24000,
+                rank: 1
+            }
+        );
+        assert_eq!(
+            report.top.iter().map(|e| e.elf).collect::<Vec<_>>(),
+            vec![4, 3, 5]
+        );
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let s = stats(&[6000, 4000, 11000, 24000, 10000]).unwrap();
+
+        assert_eq!((s.min, s.max), (4000, 24000));
+        assert_eq!(s.mean, 11000.0);
+        assert_eq!(s.median, 10000.0);
+        assert_eq!(s.percentiles[0], (10, 4000));
+        assert_eq!(s.percentiles[3], (75, 11000));
+        assert_eq!(s.histogram.len(), 10);
+        assert_eq!(s.histogram.iter().map(|b| b.count).sum::<usize>(), 5);
+        assert_eq!((s.histogram[9].from, s.histogram[9].to), (22009, 24009));
+        assert_eq!(s.histogram[9].count, 1);
+
+        assert_eq!(stats(&[1, 2]).unwrap().median, 1.5);
+        assert_eq!(stats(&[7]).unwrap().histogram.len(), 1);
+        assert!(stats(&[]).is_none());
+    }
+
+    #[test]
+    fn statistics_of_huge_totals() {
+        let s = stats(&[u64::MAX - 1, u64::MAX]).unwrap();
+        assert_eq!(s.median, u64::MAX as f64);
+        assert_eq!(s.histogram.len(), 2);
+        assert_eq!(
+            (s.histogram[1].from, s.histogram[1].to),
+            (u64::MAX, u64::MAX)
+        );
+        assert_eq!(s.histogram[1].count, 1);
+
+        let s = stats(&[0, 1, u64::MAX]).unwrap();
+        assert_eq!(s.histogram.len(), 10);
+        assert_eq!(s.histogram[0].count, 2);
+        assert_eq!(s.histogram[9].count, 1);
+        assert_eq!(s.histogram[9].to, u64::MAX);
+    }
+
+    #[test]
+    fn exports_csv_and_json() {
+        let report = Report::new(&parse(EXAMPLE).unwrap(), 3);
+
+        let csv = report.render(Format::Csv).unwrap();
+        assert!(csv.starts_with("elf,line,items,total,rank\n1,1,3,6000,4\n"));
+        assert!(csv.contains("\nstatistic,value\nelves,5\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(Format::Json).unwrap()).unwrap();
+        assert_eq!(json["top"][0]["total"], 24000);
+        assert_eq!(json["stats"]["median"], 10000.0);
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::Error as AnyhowError;
+use aoc_common::{
+    debug,
+    parse::parse_lines,
+    render_rows,
+    visual::{frame_from_rows, Palette, Recorder},
+    Answer, LineError,
+};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Cmd {
+    Noop,
+    Addx(isize),
+}
+
+fn cycles(cmd: &Cmd) -> u8 {
+    match cmd {
+        Cmd::Noop => 1,
+        Cmd::Addx(_) => 2,
+    }
+}
+
+impl FromStr for Cmd {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "noop" => Ok(Cmd::Noop),
+            s => {
+                let (cmd, arg) = s
+                    .split_once(' ')
+                    .ok_or(LineError::new(1, "noop or addx <value>"))?;
+                match cmd {
+                    "addx" => Ok(Cmd::Addx(
+                        arg.parse::<isize>()
+                            .map_err(|_| LineError::at(s, arg, "an integer"))?,
+                    )),
+                    _ => Err(LineError::new(1, "noop or addx <value>")),
+                }
+            }
+        }
+    }
+}
+
+/// Parses the CPU program.
+pub fn parse(input: &str) -> Result<Vec<Cmd>, AnyhowError> {
+    Ok(parse_lines(input, Cmd::from_str)?)
+}
+
+/// Runs the program, returning the total signal strength and the CRT rows.
+/// `part` only names the recording.
+fn run_crt(input: &str, part: u8) -> Result<(isize, Vec<String>), AnyhowError> {
+    let program = parse(input)?;
+
+    let mut cycle = 1;
+    let mut reg_x: isize = 1;
+
+    let mut screen = vec![vec!['.'; 40]; 6];
+
+    let mut total_strength = 0;
+
+    let palette = Palette::new([10, 10, 10])
+        .with('.', [30, 40, 30])
+        .with('#', [120, 255, 120])
+        .with('@', [255, 255, 255]);
+    let mut recorder = Recorder::for_part(10, part, palette)
+        .with_scale(8)
+        .with_delay(20);
+
+    let mut spy = |cycle_num, curr_x| {
+        let crt_pos = (cycle_num - 1) % 240;
+        let crt_x = crt_pos % 40;
+        let crt_y = crt_pos / 40;
+
+        if crt_x >= curr_x - 1 && crt_x <= curr_x + 1 {
+            screen[crt_y as usize][crt_x as usize] = '#';
+        }
+
+        recorder.step(|| {
+            let mut frame = frame_from_rows(screen.iter().map(|r| r.iter().copied()));
+            frame.insert((crt_x, crt_y), '@');
+            frame
+        });
+
+        if (cycle_num == 20 || (cycle_num - 20) % 40 == 0) && (cycle_num <= 220) {
+            let signal_strength = cycle_num * curr_x;
+
+            debug!("Cycle {}, signal strength {}", cycle_num, signal_strength);
+
+            total_strength += signal_strength;
+        }
+    };
+
+    for cmd in program.into_iter() {
+        for _ in 0..cycles(&cmd) {
+            spy(cycle, reg_x);
+            cycle += 1;
+        }
+
+        if let Cmd::Addx(v) = cmd {
+            reg_x += v;
+        };
+    }
+    spy(cycle, reg_x);
+
+    recorder.last(|| frame_from_rows(screen.iter().map(|r| r.iter().copied())));
+    recorder.finish()?;
+
+    Ok((total_strength, render_rows(screen)))
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    let (total_strength, _) = run_crt(input, 1)?;
+
+    Ok(total_strength.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    let (_, screen) = run_crt(input, 2)?;
+
+    Ok(screen.join("\n").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(13140));
+    }
+
+    #[test]
+    fn example_part2() {
+        let screen = [
+            "##..##..##..##..##..##..##..##..##..##..",
+            "###...###...###...###...###...###...###.",
+            "####....####....####....####....####....",
+            "#####.....#####.....#####.....#####.....",
+            "######......######......######......####",
+            "#######.......#######.......#######.....",
+        ];
+
+        assert_eq!(
+            solve_part2(EXAMPLE).unwrap(),
+            Answer::from(screen.join("\n"))
+        );
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+}
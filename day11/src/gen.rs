@@ -0,0 +1,86 @@
+//! Random notes on monkeys.
+//!
+//! Part 1 divides worry levels by three but never takes them modulo
+//! anything, so it can overflow on notes the puzzle wouldn't give, e.g. a
+//! monkey squaring items that keep coming back to it. Only part 2 is safe
+//! to solve on any generated notes.
+
+use crate::{Monkey, Op};
+use proptest::{collection::vec, prelude::*, sample::Index};
+
+/// Distinct primes keep the common modulo, and the square of any worry
+/// level below it, in range.
+const TEST_FACTORS: [u8; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+fn operation() -> impl Strategy<Value = (Op, Option<u8>)> {
+    prop_oneof![
+        (1..20u8).prop_map(|f| (Op::Add, Some(f))),
+        (1..20u8).prop_map(|f| (Op::Multiply, Some(f))),
+        Just((Op::Square, None)),
+    ]
+}
+
+/// Notes on 2 to 8 monkeys, none of which throws items to itself.
+pub fn monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+    (2..=8usize).prop_flat_map(|n| {
+        let monkey = (
+            vec(1..100usize, 0..5),
+            operation(),
+            any::<Index>(),
+            any::<Index>(),
+        );
+
+        (vec(monkey, n), Just(TEST_FACTORS.to_vec()).prop_shuffle()).prop_map(
+            move |(specs, factors)| {
+                specs
+                    .into_iter()
+                    .zip(factors)
+                    .enumerate()
+                    .map(|(i, ((items, (op, factor), t, f), test_factor))| {
+                        let others = (0..n).filter(|&j| j != i).collect::<Vec<_>>();
+                        Monkey {
+                            items,
+                            op,
+                            factor,
+                            test_factor,
+                            throw_true: *t.get(&others),
+                            throw_false: *f.get(&others),
+                            times_inspected: 0,
+                        }
+                    })
+                    .collect()
+            },
+        )
+    })
+}
+
+pub fn to_input(monkeys: &[Monkey]) -> String {
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let items = m.items.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+            let operation = match (m.op, m.factor) {
+                (Op::Square, _) => "old * old".to_owned(),
+                (Op::Add, Some(f)) => format!("old + {}", f),
+                (Op::Multiply, Some(f)) => format!("old * {}", f),
+                (op, None) => panic!("{:?} without a factor", op),
+            };
+
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                i,
+                items.join(", "),
+                operation,
+                m.test_factor,
+                m.throw_true,
+                m.throw_false
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn input() -> impl Strategy<Value = String> {
+    monkeys().prop_map(|monkeys| to_input(&monkeys))
+}
@@ -0,0 +1,380 @@
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{borrow_mut_elementwise, debug, parse::parse_lines, Answer, LineError};
+use std::str::FromStr;
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Multiply,
+    Square,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    items: Vec<usize>,
+    op: Op,
+    factor: Option<u8>,
+    test_factor: u8,
+    throw_true: usize,
+    throw_false: usize,
+    times_inspected: usize,
+}
+
+/// A single line of the notes on a monkey.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Note {
+    Monkey(usize),
+    Items(Vec<usize>),
+    Operation(Op, Option<u8>),
+    Test(u8),
+    Throw(bool, usize),
+}
+
+fn number<T: FromStr>(line: &str, part: &str, expected: &str) -> Result<T, LineError> {
+    part.trim()
+        .parse()
+        .map_err(|_| LineError::at(line, part.trim(), expected))
+}
+
+fn note(line: &str) -> Result<Note, LineError> {
+    let text = line.trim_start();
+
+    if let Some(rest) = text.strip_prefix("Monkey ") {
+        let n = rest.strip_suffix(':').ok_or(LineError::at(
+            line,
+            rest,
+            "a monkey number followed by :",
+        ))?;
+        Ok(Note::Monkey(number(line, n, "a monkey number")?))
+    } else if let Some(rest) = text.strip_prefix("Starting items:") {
+        let items = if rest.trim().is_empty() {
+            vec![]
+        } else {
+            rest.split(',')
+                .map(|item| number(line, item, "a worry level"))
+                .collect::<Result<_, _>>()?
+        };
+        Ok(Note::Items(items))
+    } else if let Some(rest) = text.strip_prefix("Operation: new = old ") {
+        let (op, operand) =
+            rest.split_once(' ')
+                .ok_or(LineError::at(line, rest, "+ or * and an operand"))?;
+        match (op, operand) {
+            ("*", "old") => Ok(Note::Operation(Op::Square, None)),
+            ("*", f) => Ok(Note::Operation(
+                Op::Multiply,
+                Some(number(line, f, "a factor")?),
+            )),
+            ("+", f) => Ok(Note::Operation(Op::Add, Some(number(line, f, "a term")?))),
+            (op, _) => Err(LineError::at(line, op, "+ or *")),
+        }
+    } else if let Some(rest) = text.strip_prefix("Test: divisible by ") {
+        match number(line, rest, "a test factor above 0")? {
+            0 => Err(LineError::at(line, rest, "a test factor above 0")),
+            f => Ok(Note::Test(f)),
+        }
+    } else if let Some(rest) = text.strip_prefix("If true: throw to monkey ") {
+        Ok(Note::Throw(true, number(line, rest, "a monkey number")?))
+    } else if let Some(rest) = text.strip_prefix("If false: throw to monkey ") {
+        Ok(Note::Throw(false, number(line, rest, "a monkey number")?))
+    } else {
+        Err(LineError::at(line, text, "a monkey note"))
+    }
+}
+
+/// The notes on one monkey, as far as they have been read.
+#[derive(Debug, Default)]
+struct Notes {
+    number: usize,
+    items: Vec<usize>,
+    operation: Option<(Op, Option<u8>)>,
+    test_factor: Option<u8>,
+    throw_true: Option<usize>,
+    throw_false: Option<usize>,
+}
+
+impl Notes {
+    fn build(self) -> Result<Monkey, AnyhowError> {
+        let missing = |what| anyhow!("Monkey {} has no {} note", self.number, what);
+        let (op, factor) = self.operation.ok_or_else(|| missing("Operation"))?;
+
+        Ok(Monkey {
+            items: self.items,
+            op,
+            factor,
+            test_factor: self.test_factor.ok_or_else(|| missing("Test"))?,
+            throw_true: self.throw_true.ok_or_else(|| missing("If true"))?,
+            throw_false: self.throw_false.ok_or_else(|| missing("If false"))?,
+            times_inspected: 0,
+        })
+    }
+}
+
+impl Monkey {
+    fn inspect_items(&mut self, modulo: usize, relief: bool) -> Result<(), AnyhowError> {
+        for i in 0..self.items.len() {
+            self.times_inspected += 1;
+
+            let mut worry = self.items[i];
+            match self.op {
+                Op::Add => {
+                    let f_num = self.factor.ok_or(anyhow!("Bad factor"))? as usize;
+                    worry += f_num;
+                }
+                Op::Multiply => {
+                    let f_num = self.factor.ok_or(anyhow!("Bad factor"))? as usize;
+                    worry *= f_num;
+                }
+                Op::Square => {
+                    worry *= worry;
+                }
+            }
+            // No relief in part 2, so the worry has to be kept in check by
+            // the common modulo of all test factors instead.
+            if relief {
+                worry /= 3;
+            } else {
+                worry %= modulo;
+            }
+
+            self.items[i] = worry;
+        }
+
+        Ok(())
+    }
+
+    fn who_next(&self) -> Vec<usize> {
+        self.items
+            .iter()
+            .map(|worry| {
+                if worry % self.test_factor as usize == 0 {
+                    self.throw_true
+                } else {
+                    self.throw_false
+                }
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+fn do_round(
+    mut monkeys: Vec<Monkey>,
+    modulo: usize,
+    relief: bool,
+) -> Result<Vec<Monkey>, AnyhowError> {
+    let mut mut_monkeys = borrow_mut_elementwise(&mut monkeys);
+    for i in 0..mut_monkeys.len() {
+        mut_monkeys[i].inspect_items(modulo, relief)?;
+
+        let targets = mut_monkeys[i].who_next();
+
+        for (j, &who) in targets.iter().enumerate() {
+            let what = mut_monkeys[i].items[j];
+
+            mut_monkeys[who].items.push(what);
+        }
+        mut_monkeys[i].items.clear();
+    }
+
+    Ok(monkeys)
+}
+
+fn print_monkeys(monkeys: &[Monkey]) {
+    for (i, m) in monkeys.iter().enumerate() {
+        debug!(
+            "Monkey {}: {:?} (inspected {} times)",
+            i, m.items, m.times_inspected
+        );
+    }
+}
+
+/// Parses the notes on every monkey. Outside strict mode a monkey whose
+/// starting items were skipped starts with none.
+pub fn parse(input: &str) -> Result<Vec<Monkey>, AnyhowError> {
+    let mut monkeys: Vec<Monkey> = vec![];
+    let mut current: Option<Notes> = None;
+
+    for n in parse_lines(input, note)? {
+        if let Note::Monkey(number) = n {
+            if let Some(notes) = current.take() {
+                monkeys.push(notes.build()?);
+            }
+            if number != monkeys.len() {
+                bail!(
+                    "Expected the notes on monkey {}, found monkey {}",
+                    monkeys.len(),
+                    number
+                );
+            }
+            current = Some(Notes {
+                number,
+                ..Notes::default()
+            });
+            continue;
+        }
+
+        let notes = current
+            .as_mut()
+            .ok_or(anyhow!("Notes before the first Monkey line"))?;
+        match n {
+            Note::Monkey(_) => unreachable!("handled above"),
+            Note::Items(items) => notes.items = items,
+            Note::Operation(op, factor) => notes.operation = Some((op, factor)),
+            Note::Test(factor) => notes.test_factor = Some(factor),
+            Note::Throw(true, to) => notes.throw_true = Some(to),
+            Note::Throw(false, to) => notes.throw_false = Some(to),
+        }
+    }
+    if let Some(notes) = current {
+        monkeys.push(notes.build()?);
+    }
+
+    for (i, m) in monkeys.iter().enumerate() {
+        for to in [m.throw_true, m.throw_false] {
+            if to >= monkeys.len() {
+                bail!("Monkey {} throws to monkey {}, which has no notes", i, to);
+            }
+        }
+    }
+
+    Ok(monkeys)
+}
+
+fn monkey_business(input: &str, rounds: usize, relief: bool) -> Result<usize, AnyhowError> {
+    let monkeys = parse(input)?;
+
+    let modulo = &monkeys
+        .iter()
+        .map(|m| m.test_factor as usize)
+        .reduce(|acc, it| acc * it)
+        .ok_or(anyhow!("No modulo!"))?;
+
+    debug!("Worry levels modulo {}", modulo);
+
+    let mut current_monkeys = monkeys;
+
+    for round in 1..=rounds {
+        current_monkeys = do_round(current_monkeys.clone(), *modulo, relief)?;
+
+        if round % 1000 == 0 {
+            debug!("===== Round {} =====", round);
+            print_monkeys(&current_monkeys);
+            debug!();
+        }
+    }
+
+    let mut counts = current_monkeys
+        .into_iter()
+        .map(|m| m.times_inspected)
+        .collect::<Vec<_>>();
+
+    counts.sort_by(|a, b| b.cmp(a));
+
+    let monkey_business = counts
+        .into_iter()
+        .take(2)
+        .reduce(|acc, it| acc * it)
+        .ok_or(anyhow!("No monkey business!"))?;
+
+    Ok(monkey_business)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(monkey_business(input, 20, true)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(monkey_business(input, 10_000, false)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{golden, ParseReport};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(10605));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(2713310158));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    #[test]
+    fn rejects_malformed_notes() {
+        let input = EXAMPLE.replacen("79, 98", "79, 9x", 1);
+        let report = parse(&input)
+            .unwrap_err()
+            .downcast::<ParseReport>()
+            .unwrap();
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!((report.errors[0].line, report.errors[0].column), (2, 23));
+        assert_eq!(report.errors[0].expected, "a worry level");
+
+        let input = EXAMPLE.replacen("old * 19", "old - 19", 1);
+        let report = parse(&input)
+            .unwrap_err()
+            .downcast::<ParseReport>()
+            .unwrap();
+        assert_eq!(report.errors[0].expected, "+ or *");
+
+        let input = EXAMPLE.replacen("throw to monkey 3", "throw to monkey 7", 1);
+        assert_eq!(
+            parse(&input).unwrap_err().to_string(),
+            "Monkey 0 throws to monkey 7, which has no notes"
+        );
+        assert_eq!(
+            parse(&EXAMPLE.replacen("  Test: divisible by 23\n", "", 1))
+                .unwrap_err()
+                .to_string(),
+            "Monkey 0 has no Test note"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn parses_generated_notes(monkeys in gen::monkeys()) {
+            prop_assert_eq!(parse(&gen::to_input(&monkeys)).unwrap(), monkeys);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn item_order_does_not_matter(monkeys in gen::monkeys()) {
+            let reversed = monkeys
+                .iter()
+                .cloned()
+                .map(|mut m| {
+                    m.items.reverse();
+                    m
+                })
+                .collect::<Vec<_>>();
+
+            // Fewer rounds than part 2 to keep debug builds quick
+            prop_assert_eq!(
+                monkey_business(&gen::to_input(&monkeys), 200, false).unwrap(),
+                monkey_business(&gen::to_input(&reversed), 200, false).unwrap()
+            );
+        }
+    }
+}
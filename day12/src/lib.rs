@@ -0,0 +1,184 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::{
+    debug,
+    grid::Pos,
+    visual::{frame_from_rows, Frame, Palette, Recorder},
+    Answer, Grid,
+};
+use pathfinding::prelude::astar;
+
+fn distance(a: &Pos, b: &Pos) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// The map as letters, with the path drawn over it.
+fn display(map: &Grid<u8>, start: &Pos, finish: &Pos, path: &[Pos]) -> Grid<char> {
+    let mut display = map.map(|h| (h + 97) as char);
+
+    for &p in path {
+        display[p] = '.';
+    }
+
+    display[*start] = 'S';
+    display[*finish] = 'E';
+
+    display
+}
+
+fn print_map(map: &Grid<u8>, start: &Pos, finish: &Pos, path: &[Pos]) {
+    debug!(
+        "{}",
+        display(map, start, finish, path).render(|&c| c).join("\n")
+    );
+}
+
+fn map_frame(map: &Grid<u8>, start: &Pos, finish: &Pos, path: &[Pos]) -> Frame {
+    frame_from_rows(
+        display(map, start, finish, path)
+            .rows()
+            .map(|r| r.iter().copied()),
+    )
+}
+
+/// Heights from dark green valleys to white peaks, the path in red.
+fn palette() -> Palette {
+    ('a'..='z')
+        .zip(0u8..)
+        .fold(Palette::new([0, 0, 0]), |p, (c, i)| {
+            p.with(c, [i * 7, 60 + i * 7, i * 7])
+        })
+        .with('.', [220, 40, 40])
+        .with('S', [255, 220, 0])
+        .with('E', [255, 220, 0])
+}
+
+/// The height map with the current position `S` and the best signal spot `E`.
+#[derive(Debug, Clone)]
+pub struct Heightmap {
+    pub map: Grid<u8>,
+    pub start: Pos,
+    pub finish: Pos,
+}
+
+/// Parses the height map, where `a` to `z` become heights 0 to 25.
+pub fn parse(input: &str) -> Result<Heightmap, AnyhowError> {
+    let chars = Grid::parse(input, "a height from a to z, S or E", |c| match c {
+        'a'..='z' | 'S' | 'E' => Some(c),
+        _ => None,
+    })?;
+
+    let find = |target| chars.iter().find(|(_, &c)| c == target).map(|(pos, _)| pos);
+    let start = find('S').ok_or(anyhow!("No start position S"))?;
+    let finish = find('E').ok_or(anyhow!("No signal position E"))?;
+
+    let map = chars.map(|&c| match c {
+        'S' => 0,
+        'E' => 26,
+        c => (c as u8) - 97,
+    });
+
+    Ok(Heightmap { map, start, finish })
+}
+
+/// Length of the shortest climb to `E`, either from `S` or from the best of
+/// all the lowest squares.
+fn climb(input: &str, any_start: bool) -> Result<usize, AnyhowError> {
+    let Heightmap { map, start, finish } = parse(input)?;
+
+    debug!(
+        "Map of {}x{}, from {:?} to {:?}",
+        map.width(),
+        map.height(),
+        start,
+        finish
+    );
+    print_map(&map, &start, &finish, &[]);
+
+    let possible_starts: Vec<Pos> = if any_start {
+        map.iter()
+            .filter(|(_, &h)| h == 0)
+            .map(|(pos, _)| pos)
+            .collect()
+    } else {
+        vec![start]
+    };
+
+    debug!("{} possible starts", possible_starts.len());
+
+    let mut min_len: usize = 9999999999999;
+    let mut min_path: Option<Vec<Pos>> = None;
+    let mut min_start: Pos = start;
+
+    for candidate in possible_starts {
+        let presult = astar(
+            &candidate,
+            |&p| {
+                map.neighbors4(p)
+                    .filter(|&n| map[n] <= map[p] + 1)
+                    .map(|n| (n, 1))
+                    .collect::<Vec<_>>()
+            },
+            |p| distance(p, &finish),
+            |p| *p == finish,
+        );
+
+        if let Some((path, plen)) = presult {
+            if plen < min_len {
+                min_path = Some(path);
+                min_len = plen;
+                min_start = candidate;
+            }
+        }
+    }
+
+    let min_path = min_path.ok_or(anyhow!("No path found!"))?;
+    print_map(&map, &min_start, &finish, &min_path);
+
+    let part = if any_start { 2 } else { 1 };
+    let mut recorder = Recorder::for_part(12, part, palette()).with_sampling(4);
+    for i in 0..min_path.len() {
+        recorder.step(|| map_frame(&map, &min_start, &finish, &min_path[..=i]));
+    }
+    recorder.last(|| map_frame(&map, &min_start, &finish, &min_path));
+    recorder.finish()?;
+
+    debug!("Shortest climb of {} from {:?}", min_len, min_start);
+
+    Ok(min_len)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(climb(input, false)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(climb(input, true)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(31));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(29));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+}
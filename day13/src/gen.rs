@@ -0,0 +1,30 @@
+//! Random packets.
+
+use crate::PacketData;
+use proptest::{collection::vec, prelude::*};
+
+/// Any packet data, a number or a list.
+pub fn data() -> impl Strategy<Value = PacketData> {
+    any::<u8>()
+        .prop_map(PacketData::Just)
+        .prop_recursive(4, 32, 5, |inner| {
+            vec(inner, 0..5).prop_map(PacketData::Nested)
+        })
+}
+
+/// A packet, which is always a list.
+pub fn packet() -> impl Strategy<Value = PacketData> {
+    vec(data(), 0..5).prop_map(PacketData::Nested)
+}
+
+pub fn to_input(pairs: &[(PacketData, PacketData)]) -> String {
+    pairs
+        .iter()
+        .map(|(left, right)| format!("{}\n{}\n", left, right))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn input() -> impl Strategy<Value = String> {
+    vec((packet(), packet()), 1..20).prop_map(|pairs| to_input(&pairs))
+}
@@ -0,0 +1,287 @@
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{debug, parse::parse_lines, Answer, LineError};
+use itertools::Itertools;
+use std::{cmp, fmt::Display, str::FromStr};
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketData {
+    Just(u8),
+    Nested(Vec<PacketData>),
+}
+
+impl Display for PacketData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Just(n) => {
+                write!(f, "{}", n)
+            }
+            Self::Nested(p) => {
+                write!(f, "[")?;
+                if let Some((tail, head)) = p.split_last() {
+                    for nested in head {
+                        write!(f, "{}, ", nested)?;
+                    }
+                    write!(f, "{}", tail)?;
+                }
+                write!(f, "]")?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Moves the number that started at `start`, if any, into `list`.
+fn take_number(
+    s: &str,
+    start: &mut Option<usize>,
+    end: usize,
+    list: &mut Vec<PacketData>,
+) -> Result<(), LineError> {
+    if let Some(start) = start.take() {
+        let num: u8 = s[start..end]
+            .parse()
+            .map_err(|_| LineError::at(s, &s[start..], "a number from 0 to 255"))?;
+        list.push(PacketData::Just(num));
+    }
+
+    Ok(())
+}
+
+impl FromStr for PacketData {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut current: Vec<Vec<PacketData>> = vec![];
+        let mut num_start: Option<usize> = None;
+        let mut packet = None;
+
+        for (i, c) in s.char_indices() {
+            if packet.is_some() {
+                return Err(LineError::at(s, &s[i..], "end of line"));
+            }
+
+            match c {
+                '[' if num_start.is_none() => {
+                    current.push(vec![]);
+                }
+                '0'..='9' if !current.is_empty() => {
+                    num_start.get_or_insert(i);
+                }
+                ',' => {
+                    let list = current.last_mut().ok_or(LineError::at(s, &s[i..], "'['"))?;
+                    take_number(s, &mut num_start, i, list)?;
+                }
+                // As written by `Display`
+                ' ' if s[..i].trim_end().ends_with(',') => {}
+                ']' => {
+                    let mut list = current.pop().ok_or(LineError::at(s, &s[i..], "'['"))?;
+                    take_number(s, &mut num_start, i, &mut list)?;
+
+                    match current.last_mut() {
+                        Some(parent) => parent.push(PacketData::Nested(list)),
+                        None => packet = Some(PacketData::Nested(list)),
+                    }
+                }
+                _ if current.is_empty() => {
+                    return Err(LineError::at(s, &s[i..], "'['"));
+                }
+                _ => {
+                    return Err(LineError::at(s, &s[i..], "'[', ']', ',' or a digit"));
+                }
+            };
+        }
+
+        packet.ok_or(LineError::new(s.chars().count() + 1, "']'"))
+    }
+}
+
+impl PartialOrd for PacketData {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PacketData {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        match (self, other) {
+            (PacketData::Just(a), PacketData::Just(b)) => a.cmp(b),
+            (a @ PacketData::Nested(_), b @ PacketData::Just(_)) => {
+                a.cmp(&PacketData::Nested(vec![b.clone()]))
+            }
+            (a @ PacketData::Just(_), b @ PacketData::Nested(_)) => {
+                PacketData::Nested(vec![a.clone()]).cmp(b)
+            }
+            (PacketData::Nested(a), PacketData::Nested(b)) => {
+                let mut result = cmp::Ordering::Equal;
+                for i in 0..cmp::max(a.len(), b.len()) {
+                    let ai = a.get(i);
+                    let bi = b.get(i);
+
+                    if ai.is_none() {
+                        result = cmp::Ordering::Less;
+                    } else if bi.is_none() {
+                        result = cmp::Ordering::Greater;
+                    } else {
+                        let outcome = match ai.partial_cmp(&bi) {
+                            ord @ Some(cmp::Ordering::Greater) => ord,
+                            ord @ Some(cmp::Ordering::Less) => ord,
+                            _ => None,
+                        };
+
+                        if let Some(res) = outcome {
+                            result = res;
+                            break;
+                        }
+                    }
+                }
+                result
+            }
+        }
+    }
+}
+
+/// Parses the distress signal into pairs of packets.
+pub fn parse(input: &str) -> Result<Vec<(PacketData, PacketData)>, AnyhowError> {
+    let packets = parse_lines(input, PacketData::from_str)?;
+    if packets.len() % 2 != 0 {
+        bail!("Packet {} has no pair", packets.len());
+    }
+
+    Ok(packets.into_iter().tuples().collect())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    let pairs = parse(input)?;
+
+    let mut sum = 0;
+    for (i, (lp, rp)) in pairs.iter().enumerate() {
+        if lp.cmp(rp) == cmp::Ordering::Less {
+            sum += i + 1;
+        }
+
+        debug!("{}\n{}\nlp {:?} rp\n", lp, rp, lp.cmp(rp));
+    }
+
+    Ok(sum.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    let pairs = parse(input)?;
+
+    let mut unpaired = pairs
+        .into_iter()
+        .flat_map(|p| vec![p.0, p.1])
+        .collect::<Vec<_>>();
+
+    let div_one = "[[2]]".parse::<PacketData>()?;
+    let div_two = "[[6]]".parse::<PacketData>()?;
+
+    unpaired.push(div_one.clone());
+    unpaired.push(div_two.clone());
+
+    unpaired.sort();
+
+    for pkt in &unpaired {
+        debug!("{}", pkt);
+    }
+
+    let idx_one = unpaired
+        .binary_search(&div_one)
+        .map_err(|_| anyhow!("Divider not found"))?
+        + 1;
+
+    let idx_two = unpaired
+        .binary_search(&div_two)
+        .map_err(|_| anyhow!("Divider not found"))?
+        + 1;
+
+    Ok((idx_one * idx_two).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use proptest::{collection::vec, prelude::*};
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(13));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(140));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    #[test]
+    fn spaces_may_follow_commas() {
+        let packet = "[1, [2,  3], []]".parse::<PacketData>().unwrap();
+        assert_eq!(packet, "[1,[2,3],[]]".parse().unwrap());
+        assert_eq!(packet.to_string(), "[1, [2, 3], []]");
+
+        let err = "[1 ,2]".parse::<PacketData>().unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (3, "'[', ']', ',' or a digit")
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trips(packet in gen::packet()) {
+            prop_assert_eq!(packet.to_string().parse::<PacketData>().unwrap(), packet);
+        }
+
+        #[test]
+        fn order_is_antisymmetric(left in gen::data(), right in gen::data()) {
+            prop_assert_eq!(left.cmp(&right), right.cmp(&left).reverse());
+        }
+
+        #[test]
+        fn solves_generated_pairs(pairs in vec((gen::packet(), gen::packet()), 1..20)) {
+            let input = gen::to_input(&pairs);
+            prop_assert_eq!(parse(&input).unwrap(), pairs.clone());
+
+            let ordered = pairs
+                .iter()
+                .enumerate()
+                .filter(|(_, (left, right))| left < right)
+                .map(|(i, _)| i + 1)
+                .sum::<usize>();
+            prop_assert_eq!(solve_part1(&input).unwrap(), ordered.into());
+
+            // Packets ordered like a divider leave its place ambiguous
+            let dividers = ["[[2]]", "[[6]]"].map(|d| d.parse::<PacketData>().unwrap());
+            prop_assume!(pairs
+                .iter()
+                .flat_map(|(left, right)| [left, right])
+                .all(|p| dividers.iter().all(|d| p.cmp(d) != cmp::Ordering::Equal)));
+
+            let before = |divider: &PacketData| {
+                pairs
+                    .iter()
+                    .flat_map(|(left, right)| [left, right])
+                    .filter(|p| *p < divider)
+                    .count()
+            };
+            let key = (before(&dividers[0]) + 1) * (before(&dividers[1]) + 2);
+            prop_assert_eq!(solve_part2(&input).unwrap(), key.into());
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Random rock traces around the sand source.
+
+use proptest::{collection::vec, prelude::*};
+
+/// A rock path as its corners, each segment horizontal or vertical. Rock
+/// stays below the source.
+pub fn trace() -> impl Strategy<Value = Vec<(isize, isize)>> {
+    let start = (480..=520isize, 1..=30isize);
+    let segment = (any::<bool>(), -6..=6isize);
+
+    (start, vec(segment, 1..5)).prop_map(|(start, segments)| {
+        let mut corners = vec![start];
+        for (horizontal, len) in segments {
+            let (x, y) = *corners.last().unwrap();
+            corners.push(if horizontal {
+                (x + len, y)
+            } else {
+                (x, (y + len).max(1))
+            });
+        }
+        corners
+    })
+}
+
+pub fn to_input(traces: &[Vec<(isize, isize)>]) -> String {
+    traces
+        .iter()
+        .map(|trace| {
+            let points = trace
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .collect::<Vec<_>>();
+            format!("{}\n", points.join(" -> "))
+        })
+        .collect()
+}
+
+pub fn input() -> impl Strategy<Value = String> {
+    vec(trace(), 1..8).prop_map(|traces| to_input(&traces))
+}
@@ -0,0 +1,277 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::{
+    debug,
+    grid::Pos,
+    parse::parse_lines,
+    visual::{Frame, Palette, Recorder},
+    Answer, LineError, SparseGrid,
+};
+use std::cmp;
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Rock,
+    Sand,
+}
+
+/// Where the sand pours in from.
+const SOURCE: Pos = (500, 0);
+
+/// Parses the rock paths into a scan of the cave.
+pub fn parse(input: &str) -> Result<SparseGrid<Tile>, AnyhowError> {
+    let rock_traces = parse_lines(input, |s| {
+        s.split(" -> ")
+            .map(|p| {
+                let (x, y) = p
+                    .split_once(',')
+                    .ok_or(LineError::at(s, p, "a point like 498,4"))?;
+                let x: isize = x
+                    .parse()
+                    .map_err(|_| LineError::at(s, x, "an x coordinate"))?;
+                let y: isize = y
+                    .parse()
+                    .map_err(|_| LineError::at(s, y, "a y coordinate"))?;
+
+                Ok((x, y))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    let cave = rock_traces
+        .into_iter()
+        .flat_map(|path| {
+            path.windows(2)
+                .flat_map(|w| {
+                    let start = w[0];
+                    let finish = w[1];
+                    let horizontal = start.1 == finish.1;
+
+                    if horizontal {
+                        let min_x = cmp::min(start.0, finish.0);
+                        let max_x = cmp::max(start.0, finish.0);
+
+                        (min_x..=max_x).map(|x| (x, start.1)).collect::<Vec<_>>()
+                    } else {
+                        let min_y = cmp::min(start.1, finish.1);
+                        let max_y = cmp::max(start.1, finish.1);
+
+                        (min_y..=max_y).map(|y| (start.0, y)).collect::<Vec<_>>()
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .map(|p| (p, Tile::Rock))
+        .collect();
+
+    Ok(cave)
+}
+
+/// The cave with rock as `#`, resting sand as `o`, falling sand as `~` and
+/// the source as `+`.
+fn cave_frame(cave: &SparseGrid<Tile>, falling: &[Pos]) -> Frame {
+    let mut frame: Frame = cave
+        .iter()
+        .map(|(p, tile)| match tile {
+            Tile::Rock => (p, '#'),
+            Tile::Sand => (p, 'o'),
+        })
+        .collect();
+
+    for &p in falling {
+        frame.insert(p, '~');
+    }
+    frame.insert(SOURCE, '+');
+
+    frame
+}
+
+fn print_cave(cave: &SparseGrid<Tile>, falling: &[Pos]) {
+    if !debug::is_enabled() {
+        return;
+    }
+
+    let display = cave_frame(cave, falling);
+    let field = display.render('.', |&c| c);
+
+    debug!("{:?}\n{}", display.bounds(), field.join("\n"));
+}
+
+/// Pours sand until it either starts falling into the abyss or, when there is
+/// a floor two units below the lowest rock, blocks the source. Returns the
+/// number of units that came to rest.
+fn pour_sand(input: &str, floor: bool) -> Result<usize, AnyhowError> {
+    let mut cave = parse(input)?;
+    print_cave(&cave, &[]);
+
+    let mut sand_rested: usize = 0;
+    let mut i = 0;
+
+    let max_rock_y = cave
+        .bounds()
+        .map(|(_, max)| max.1)
+        .ok_or(anyhow!("No floor!"))?;
+
+    let mut sands: Vec<Pos> = vec![SOURCE];
+
+    let palette = Palette::new([20, 20, 30])
+        .with('#', [170, 70, 50])
+        .with('o', [230, 190, 90])
+        .with('~', [255, 240, 170])
+        .with('+', [255, 255, 255]);
+    let part = if floor { 2 } else { 1 };
+    let mut recorder = Recorder::for_part(14, part, palette)
+        .with_scale(2)
+        .with_sampling(if floor { 500 } else { 50 });
+
+    'outer: loop {
+        let mut survived: Vec<Pos> = vec![];
+
+        for &sand in &sands {
+            let candidate = [
+                (sand.0, sand.1 + 1),
+                (sand.0 - 1, sand.1 + 1),
+                (sand.0 + 1, sand.1 + 1),
+            ]
+            .into_iter()
+            .find(|&p| !cave.contains(p) && (!floor || p.1 < (max_rock_y + 2)));
+
+            if !floor && sand.1 > max_rock_y {
+                break 'outer;
+            }
+
+            if let Some(next_sand) = candidate {
+                survived.push(next_sand);
+            } else {
+                cave.insert(sand, Tile::Sand);
+                sand_rested += 1;
+            }
+
+            if cave.contains(SOURCE) {
+                break 'outer;
+            }
+        }
+
+        sands = survived;
+        sands.push(SOURCE);
+
+        recorder.step(|| cave_frame(&cave, &sands));
+
+        if i % 1000 == 0 {
+            debug!("Iteration {}, sand rested {}", i, sand_rested);
+        }
+
+        if i % 10_000 == 0 {
+            print_cave(&cave, &sands);
+        }
+
+        i += 1;
+    }
+
+    debug!("Total iterations: {}", i);
+    debug!("Sand units rested: {}", sand_rested);
+
+    print_cave(&cave, &sands);
+
+    recorder.last(|| cave_frame(&cave, &sands));
+    recorder.finish()?;
+
+    Ok(sand_rested)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(pour_sand(input, false)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(pour_sand(input, true)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use proptest::{collection::vec, prelude::*};
+    use std::collections::HashSet;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(24));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(93));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    #[ignore = "slow: pours 25k units of sand"]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    /// Rock of the traces, drawn point by point.
+    fn rock(traces: &[Vec<Pos>]) -> HashSet<Pos> {
+        let mut rock = HashSet::new();
+        for trace in traces {
+            for w in trace.windows(2) {
+                let (mut p, end) = (w[0], w[1]);
+                rock.insert(p);
+                while p != end {
+                    p.0 += (end.0 - p.0).signum();
+                    p.1 += (end.1 - p.1).signum();
+                    rock.insert(p);
+                }
+            }
+        }
+        rock
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn parses_generated_traces(traces in vec(gen::trace(), 1..8)) {
+            let cave = parse(&gen::to_input(&traces)).unwrap();
+            let parsed = cave.iter().map(|(p, _)| p).collect::<HashSet<_>>();
+
+            prop_assert_eq!(parsed, rock(&traces));
+        }
+
+        #[test]
+        fn sand_fills_what_it_can_reach(traces in vec(gen::trace(), 1..8)) {
+            let input = gen::to_input(&traces);
+            let rock = rock(&traces);
+            let floor = rock.iter().map(|p| p.1).max().unwrap() + 2;
+
+            // With a floor, sand ends up in every cell it can fall into
+            let mut row = HashSet::from([SOURCE]);
+            let mut reachable = 1;
+            for y in 1..floor {
+                row = row
+                    .iter()
+                    .flat_map(|&(x, _)| [(x - 1, y), (x, y), (x + 1, y)])
+                    .filter(|p| !rock.contains(p))
+                    .collect();
+                reachable += row.len();
+            }
+
+            let part1 = solve_part1(&input).unwrap();
+            let part2 = solve_part2(&input).unwrap();
+            prop_assert_eq!(&part2, &reachable.into());
+            prop_assert!(matches!(
+                (part1, part2),
+                (Answer::Number(p1), Answer::Number(p2)) if p1 <= p2
+            ));
+        }
+    }
+}
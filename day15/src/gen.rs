@@ -0,0 +1,67 @@
+//! Random sensor reports over a small area.
+
+use proptest::{collection::vec, prelude::*};
+
+type Pos = (isize, isize);
+
+/// A sensor and its closest beacon.
+pub type Reading = (Pos, Pos);
+
+fn distance(a: Pos, b: Pos) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// A sensor around `0..=max`, with a beacon close by.
+pub fn reading(max: isize) -> impl Strategy<Value = Reading> {
+    ((-5..=max + 5, -5..=max + 5), (-8..=8isize, -8..=8isize))
+        .prop_map(|(s, (dx, dy))| (s, (s.0 + dx, s.1 + dy)))
+}
+
+pub fn readings(max: isize) -> impl Strategy<Value = Vec<Reading>> {
+    vec(reading(max), 1..10)
+}
+
+/// Readings leaving a single position within `0..=max` uncovered: random
+/// ones missing it, then one more on each other position still uncovered,
+/// reaching just short of it.
+pub fn with_hole(max: isize) -> impl Strategy<Value = Vec<Reading>> {
+    ((0..=max, 0..=max), readings(max)).prop_map(move |(hole, readings)| {
+        let mut readings = readings
+            .into_iter()
+            .filter(|&(s, b)| distance(s, b) < distance(s, hole))
+            .collect::<Vec<_>>();
+
+        for y in 0..=max {
+            for x in 0..=max {
+                let p = (x, y);
+                if p != hole
+                    && readings
+                        .iter()
+                        .all(|&(s, b)| distance(s, p) > distance(s, b))
+                {
+                    let radius = distance(p, hole) - 1;
+                    readings.push((p, (x + radius as isize, y)));
+                }
+            }
+        }
+
+        readings
+    })
+}
+
+pub fn to_input(readings: &[Reading]) -> String {
+    readings
+        .iter()
+        .map(|((sx, sy), (bx, by))| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                sx, sy, bx, by
+            )
+        })
+        .collect()
+}
+
+/// Reports on the area of the example, `0..=20`.
+pub fn input() -> impl Strategy<Value = String> {
+    with_hole(20).prop_map(|readings| to_input(&readings))
+}
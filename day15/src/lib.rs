@@ -0,0 +1,225 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::{debug, parse::parse_lines, Answer, LineError};
+use regex::Regex;
+use std::{cmp, ops::RangeInclusive};
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+#[cfg(any(test, feature = "reference"))]
+pub mod reference;
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: isize,
+    y: isize,
+}
+
+impl Point {
+    fn distance_to(&self, tgt: &Point) -> usize {
+        self.x.abs_diff(tgt.x) + self.y.abs_diff(tgt.y)
+    }
+}
+
+#[derive(Debug)]
+struct Sensor(Point);
+
+#[derive(Debug)]
+struct Beacon(Point);
+
+impl Beacon {
+    fn frequency(&self) -> isize {
+        self.0.x * 4000000 + self.0.y
+    }
+}
+
+/// The area a sensor covers: every point within its beacon's distance.
+#[derive(Debug)]
+pub struct Circle {
+    center: Point,
+    radius: usize,
+    beacon: Point,
+}
+
+fn scan_y(inspected_y: isize, circles: &[Circle]) -> Vec<RangeInclusive<isize>> {
+    let mut intersections = circles
+        .iter()
+        .filter(|c| c.center.y.abs_diff(inspected_y) <= c.radius)
+        .map(|c| {
+            let height = c.center.y.abs_diff(inspected_y);
+            let width_at_height = c.radius - height;
+            RangeInclusive::new(
+                c.center.x - width_at_height as isize,
+                c.center.x + width_at_height as isize,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    intersections.sort_by(|a, b| a.start().cmp(b.start()));
+
+    // Merge overlapping and adjacent ranges, so any gap left is a real one.
+    let mut merged: Vec<RangeInclusive<isize>> = vec![];
+    for r in intersections {
+        match merged.last_mut() {
+            Some(last) if *r.start() <= last.end() + 1 => {
+                *last = RangeInclusive::new(*last.start(), cmp::max(*last.end(), *r.end()));
+            }
+            _ => merged.push(r),
+        }
+    }
+
+    merged
+}
+
+/// Parses the sensor report into the area covered by each sensor.
+pub fn parse(input: &str) -> Result<Vec<Circle>, AnyhowError> {
+    let regexp =
+        Regex::new(r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$")?;
+
+    let circles = parse_lines(input, |s| {
+        let cap = regexp.captures(s).ok_or(LineError::new(
+            1,
+            "Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>",
+        ))?;
+        let coord = |i: usize| {
+            cap[i]
+                .parse::<isize>()
+                .map_err(|_| LineError::at(s, &cap[i], "a coordinate"))
+        };
+
+        Ok((
+            Sensor(Point {
+                x: coord(1)?,
+                y: coord(2)?,
+            }),
+            Beacon(Point {
+                x: coord(3)?,
+                y: coord(4)?,
+            }),
+        ))
+    })?
+    .into_iter()
+    .map(|(s, b)| Circle {
+        center: s.0,
+        radius: s.0.distance_to(&b.0),
+        beacon: b.0,
+    })
+    .collect::<Vec<_>>();
+
+    Ok(circles)
+}
+
+/// Counts the positions in row `y` where no beacon can be present: those
+/// covered by a sensor, but for the beacons already known there.
+pub fn count_covered(input: &str, y: isize) -> Result<isize, AnyhowError> {
+    let circles = parse(input)?;
+
+    let intersections = scan_y(y, &circles);
+    debug!("Row {} covered by {:?}", y, intersections);
+
+    // Every beacon is covered by the sensor it is closest to
+    let mut beacons = circles
+        .iter()
+        .filter(|c| c.beacon.y == y)
+        .map(|c| c.beacon.x)
+        .collect::<Vec<_>>();
+    beacons.sort_unstable();
+    beacons.dedup();
+
+    let covered = intersections
+        .into_iter()
+        .map(|r| r.end() - r.start() + 1)
+        .sum::<isize>();
+
+    Ok(covered - beacons.len() as isize)
+}
+
+/// Finds the only position with both coordinates within `0..=max` not covered
+/// by any sensor and returns its tuning frequency.
+pub fn find_distress_beacon(input: &str, max: isize) -> Result<isize, AnyhowError> {
+    let circles = parse(input)?;
+
+    let mut beacon: Option<Beacon> = None;
+    for inspected_y in 0..=max {
+        let intersections = scan_y(inspected_y, &circles)
+            .into_iter()
+            .map(|r| RangeInclusive::new(cmp::max(*r.start(), 0), cmp::min(*r.end(), max)))
+            .filter(|r| !r.is_empty())
+            .collect::<Vec<_>>();
+
+        // The ranges are sorted and merged, so the first gap is before the
+        // first range not starting right after the previous one
+        let mut x = 0;
+        for r in &intersections {
+            if *r.start() > x {
+                break;
+            }
+            x = r.end() + 1;
+        }
+
+        if x <= max {
+            debug!("Row {} covered by {:?}", inspected_y, intersections);
+            beacon = Some(Beacon(Point { x, y: inspected_y }));
+            break;
+        }
+    }
+
+    let beacon = beacon.ok_or(anyhow!("No beacon!"))?;
+
+    debug!("Beacon at {:?}, frequency {}", beacon, beacon.frequency());
+
+    Ok(beacon.frequency())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(count_covered(input, 2_000_000)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(find_distress_beacon(input, 4_000_000)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(count_covered(EXAMPLE, 10).unwrap(), 26);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(find_distress_beacon(EXAMPLE, 20).unwrap(), 56000011);
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    #[test]
+    fn reference_example() {
+        assert_eq!(reference::count_covered(EXAMPLE, 10).unwrap(), 26);
+        assert_eq!(
+            reference::find_distress_beacon(EXAMPLE, 20).unwrap(),
+            56000011
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn generated_reports_have_one_hole(readings in gen::with_hole(20)) {
+            let input = gen::to_input(&readings);
+            prop_assert!(reference::find_distress_beacon(&input, 20).is_ok());
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Point by point solutions, to check the range merging of `scan_y` with.
+
+use crate::{parse, Beacon, Point};
+use anyhow::{bail, Error as AnyhowError};
+
+/// Counts the positions in row `y` covered by a sensor that hold no beacon.
+pub fn count_covered(input: &str, y: isize) -> Result<isize, AnyhowError> {
+    let circles = parse(input)?;
+
+    let min_x = circles.iter().map(|c| c.center.x - c.radius as isize).min();
+    let max_x = circles.iter().map(|c| c.center.x + c.radius as isize).max();
+
+    let mut count = 0;
+    for x in min_x.unwrap_or(0)..=max_x.unwrap_or(-1) {
+        let p = Point { x, y };
+        let covered = circles.iter().any(|c| c.center.distance_to(&p) <= c.radius);
+        let beacon = circles.iter().any(|c| c.beacon.x == x && c.beacon.y == y);
+
+        if covered && !beacon {
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+/// Finds the only position within `0..=max` not covered by any sensor and
+/// returns its tuning frequency.
+pub fn find_distress_beacon(input: &str, max: isize) -> Result<isize, AnyhowError> {
+    let circles = parse(input)?;
+
+    let mut uncovered = vec![];
+    for y in 0..=max {
+        for x in 0..=max {
+            let p = Point { x, y };
+            if circles.iter().all(|c| c.center.distance_to(&p) > c.radius) {
+                uncovered.push(p);
+            }
+        }
+    }
+
+    match uncovered[..] {
+        [p] => Ok(Beacon(p).frequency()),
+        _ => bail!("{} uncovered positions", uncovered.len()),
+    }
+}
@@ -0,0 +1,106 @@
+//! Random valve scans shaped like the puzzle's: valves with flow are joined
+//! directly or through corridors of broken valves, each with two tunnels.
+
+use crate::Valve;
+use proptest::{collection::vec, prelude::*, sample::Index};
+
+/// Id of the `i`th valve, `AA` first.
+fn id(i: usize) -> String {
+    [i % 26, i / 26]
+        .iter()
+        .map(|&l| (b'A' + l as u8) as char)
+        .collect()
+}
+
+fn link(valves: &mut [Valve], a: usize, b: usize) {
+    for (from, to) in [(a, b), (b, a)] {
+        let to = id(to);
+        if !valves[from].tunnel_to.contains(&to) {
+            valves[from].tunnel_to.push(to);
+        }
+    }
+}
+
+/// Valves from the flow rates, the edges between them and the length of
+/// the corridor each edge goes through.
+fn build(flows: &[usize], edges: &[(usize, usize)], corridors: &[usize]) -> Vec<Valve> {
+    let valve = |flow| Valve {
+        id: String::new(),
+        flow,
+        tunnel_to: vec![],
+    };
+    let mut valves = [0]
+        .iter()
+        .chain(flows)
+        .map(|&f| valve(f))
+        .collect::<Vec<_>>();
+
+    for (&(a, b), &len) in edges.iter().zip(corridors.iter().cycle()) {
+        let mut from = a;
+        for _ in 0..len {
+            valves.push(valve(0));
+            let broken = valves.len() - 1;
+            link(&mut valves, from, broken);
+            from = broken;
+        }
+        link(&mut valves, from, b);
+    }
+
+    for (i, v) in valves.iter_mut().enumerate() {
+        v.id = id(i);
+    }
+
+    valves
+}
+
+/// A connected scan of 1 to 6 valves with flow, starting at `AA`, in any
+/// order.
+pub fn valves() -> impl Strategy<Value = Vec<Valve>> {
+    (1..=6usize)
+        .prop_flat_map(|k| {
+            (
+                vec(1..25usize, k),
+                vec(any::<Index>(), k),
+                vec((any::<Index>(), any::<Index>()), 0..3),
+                vec(0..3usize, 1..4),
+            )
+        })
+        .prop_map(|(flows, parents, extra, corridors)| {
+            let k = flows.len();
+            let tree = parents
+                .iter()
+                .enumerate()
+                .map(|(i, parent)| (i + 1, parent.index(i + 1)));
+            let extra = extra
+                .iter()
+                .map(|(a, b)| (a.index(k + 1), b.index(k + 1)))
+                .filter(|(a, b)| a != b);
+
+            build(&flows, &tree.chain(extra).collect::<Vec<_>>(), &corridors)
+        })
+        .prop_shuffle()
+}
+
+pub fn to_input(valves: &[Valve]) -> String {
+    valves
+        .iter()
+        .map(|v| {
+            let tunnels = if v.tunnel_to.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            format!(
+                "Valve {} has flow rate={}; {} {}\n",
+                v.id,
+                v.flow,
+                tunnels,
+                v.tunnel_to.join(", ")
+            )
+        })
+        .collect()
+}
+
+pub fn input() -> impl Strategy<Value = String> {
+    valves().prop_map(|valves| to_input(&valves))
+}
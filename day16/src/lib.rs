@@ -0,0 +1,444 @@
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{debug, parse::parse_lines, Answer, LineError};
+use petgraph::{
+    algo::dijkstra, dot::Dot, prelude::UnGraph, stable_graph::NodeIndex, visit::IntoNodeReferences,
+    Direction, Graph,
+};
+use regex::Regex;
+use std::{
+    cmp::{self, Reverse},
+    collections::{HashMap, HashSet},
+};
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+#[cfg(any(test, feature = "reference"))]
+pub mod reference;
+
+pub type ValveGraph = UnGraph<(String, usize), usize>;
+
+/// A valve and the ones its tunnels lead to, as listed in the scan.
+#[derive(Debug, Clone)]
+pub struct Valve {
+    id: String,
+    flow: usize,
+    tunnel_to: Vec<String>,
+}
+
+fn simplify(graph: &mut ValveGraph) {
+    loop {
+        let mut to_remove: Option<NodeIndex> = None;
+        for (i, n) in graph.node_references() {
+            if n.0 != "AA" && n.1 == 0 {
+                to_remove = Some(i);
+                break;
+            }
+        }
+        if to_remove.is_none() {
+            break;
+        } else if let Some(to_remove) = to_remove {
+            let mut to_add = HashSet::new();
+
+            let incoming = graph.neighbors_directed(to_remove, Direction::Incoming);
+            let mut outgoing = graph.neighbors_directed(to_remove, Direction::Outgoing);
+
+            for a in incoming {
+                for b in outgoing.by_ref() {
+                    if a != b {
+                        if let Some(e1) = graph.find_edge(a, to_remove) {
+                            if let Some(e2) = graph.find_edge(to_remove, b) {
+                                if let Some(w1) = graph.edge_weight(e1) {
+                                    if let Some(w2) = graph.edge_weight(e2) {
+                                        to_add.insert((a, b, w1 + w2));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            for (a, b, weight) in to_add {
+                graph.add_edge(a, b, weight);
+            }
+
+            graph.remove_node(to_remove);
+        }
+    }
+
+    graph.shrink_to_fit();
+}
+
+/// Parses the valve scan, one valve per line.
+fn parse_valves(input: &str) -> Result<Vec<Valve>, AnyhowError> {
+    let regex = Regex::new(
+        r"^Valve ([A-Z]+) has flow rate=(\d+); tunnels? leads? to valves? ([A-Z]+(, [A-Z]+)*)$",
+    )?;
+
+    Ok(parse_lines(input, |s| {
+        let cap = regex.captures(s).ok_or(LineError::new(
+            1,
+            "Valve <id> has flow rate=<rate>; tunnels lead to valves <id>, ...",
+        ))?;
+
+        Ok(Valve {
+            id: cap[1].to_owned(),
+            flow: cap[2]
+                .parse()
+                .map_err(|_| LineError::at(s, &cap[2], "a flow rate"))?,
+            tunnel_to: cap[3].split(", ").map(|s| s.to_owned()).collect(),
+        })
+    })?)
+}
+
+/// Parses the valve scan into a graph simplified down to the valves worth
+/// opening, along with the start valve and the node of each valve id.
+pub fn parse(
+    input: &str,
+) -> Result<(ValveGraph, NodeIndex, HashMap<String, NodeIndex>), AnyhowError> {
+    let valves = parse_valves(input)?;
+
+    let valve_map = HashMap::<String, &Valve>::from_iter(valves.iter().map(|v| (v.id.clone(), v)));
+
+    let mut graph = Graph::new_undirected();
+    let mut graph_map: HashMap<String, NodeIndex> = HashMap::new();
+
+    for v in &valves {
+        let mut a = graph_map.get(&v.id).cloned();
+        if a.is_none() {
+            let flow = valve_map.get(&v.id).map(|v| v.flow);
+
+            let i = graph.add_node((v.id.clone(), flow.unwrap_or(0)));
+
+            graph_map.insert(v.id.clone(), i);
+            a = Some(i);
+        }
+
+        for id in &v.tunnel_to {
+            let mut b = graph_map.get(id).cloned();
+            if b.is_none() {
+                let flow = valve_map.get(id).map(|v| v.flow);
+
+                let i = graph.add_node((id.clone(), flow.unwrap_or(0)));
+
+                graph_map.insert(id.clone(), i);
+                b = Some(i);
+            }
+
+            if let Some(a) = a {
+                if let Some(b) = b {
+                    if !graph.contains_edge(a, b) {
+                        graph.add_edge(a, b, 1);
+                    }
+                }
+            }
+        }
+    }
+
+    simplify(&mut graph);
+
+    graph_map.clear();
+    for i in graph.node_indices() {
+        graph_map.insert(graph[i].0.clone(), i);
+    }
+
+    debug!("{:?}", Dot::with_config(&graph, &[]));
+
+    let start = *graph_map.get("AA").ok_or(anyhow!("No start"))?;
+
+    Ok((graph, start, graph_map))
+}
+
+#[allow(clippy::only_used_in_recursion)]
+fn follow_path(
+    graph: &ValveGraph,
+    dists: &HashMap<(NodeIndex, NodeIndex), usize>,
+    start: NodeIndex,
+    time_limit: usize,
+    visited: &mut HashSet<NodeIndex>,
+    level: usize,
+) -> (usize, Vec<String>) {
+    if time_limit == 0 {
+        return (0, vec![graph[start].0.clone()]);
+    }
+
+    let dests = graph.node_indices();
+
+    let mut max_gain = 0;
+    let mut path = vec![graph[start].0.clone()];
+
+    for end in dests {
+        let dest_flow = graph[end].1;
+
+        if dest_flow > 0 && !visited.contains(&end) {
+            if let Some(dist) = dists.get(&(start, end)) {
+                let time_to_open = dist + 1;
+
+                if time_to_open > time_limit {
+                    continue;
+                }
+
+                let gain = (time_limit - time_to_open) * dest_flow;
+
+                let backtrack_to = visited.clone();
+
+                visited.insert(end);
+                let (path_gain, sub_path) = follow_path(
+                    graph,
+                    dists,
+                    end,
+                    time_limit - time_to_open,
+                    visited,
+                    level + 1,
+                );
+
+                let total_gain = path_gain + gain;
+
+                // if level == 0 {
+                //     println!("Consider {:?} = {}", &graph[end], total_gain);
+                // }
+
+                if total_gain > max_gain {
+                    max_gain = total_gain;
+                    path = sub_path.clone();
+                    path.push(graph[start].0.clone());
+                }
+
+                visited.clear();
+                for n in backtrack_to {
+                    visited.insert(n);
+                }
+            }
+        }
+    }
+
+    (max_gain, path)
+}
+
+fn part_1(
+    graph: &ValveGraph,
+    dists: &HashMap<(NodeIndex, NodeIndex), usize>,
+    start: NodeIndex,
+) -> Result<usize, AnyhowError> {
+    let (max_gain, mut path) = follow_path(graph, dists, start, 30, &mut HashSet::new(), 0);
+
+    path.reverse();
+
+    debug!("Max gain: {}\nPath: {:?}\n", max_gain, path);
+
+    Ok(max_gain)
+}
+
+/// Most pressure released by opening each set of `valves`, as a bitmask, in
+/// some order within `time` minutes from `start`.
+fn best_by_opened(
+    graph: &ValveGraph,
+    dists: &HashMap<(NodeIndex, NodeIndex), usize>,
+    valves: &[NodeIndex],
+    start: NodeIndex,
+    time: usize,
+) -> HashMap<u64, usize> {
+    let mut best = HashMap::new();
+    let mut stack = vec![(start, time, 0u64, 0)];
+
+    while let Some((at, time_left, opened, released)) = stack.pop() {
+        let entry = best.entry(opened).or_insert(0);
+        *entry = cmp::max(*entry, released);
+
+        for (i, &valve) in valves.iter().enumerate() {
+            if opened & 1 << i != 0 {
+                continue;
+            }
+            let Some(dist) = dists.get(&(at, valve)) else {
+                continue;
+            };
+            if let Some(left) = time_left.checked_sub(dist + 1) {
+                stack.push((
+                    valve,
+                    left,
+                    opened | 1 << i,
+                    released + left * graph[valve].1,
+                ));
+            }
+        }
+    }
+
+    best
+}
+
+/// Most pressure released within 26 minutes by you and the elephant, each
+/// opening a set of valves the other leaves closed.
+fn part_2(
+    graph: &ValveGraph,
+    dists: &HashMap<(NodeIndex, NodeIndex), usize>,
+    start: NodeIndex,
+) -> Result<usize, AnyhowError> {
+    let valves = graph
+        .node_indices()
+        .filter(|&n| graph[n].1 > 0)
+        .collect::<Vec<_>>();
+    if valves.len() > 64 {
+        bail!("Too many valves with flow: {}", valves.len());
+    }
+
+    let mut best = best_by_opened(graph, dists, &valves, start, 26)
+        .into_iter()
+        .collect::<Vec<_>>();
+    best.sort_by_key(|&(_, released)| Reverse(released));
+
+    // From the sets releasing the most down, until no pair left can beat
+    // the best one found
+    let mut total_gain = 0;
+    for (i, &(yours, released)) in best.iter().enumerate() {
+        if released * 2 <= total_gain {
+            break;
+        }
+        for &(elephants, other) in &best[i..] {
+            if released + other <= total_gain {
+                break;
+            }
+            if yours & elephants == 0 {
+                total_gain = released + other;
+            }
+        }
+    }
+
+    debug!("Total gain: {}", total_gain);
+
+    Ok(total_gain)
+}
+
+fn distances(graph: &ValveGraph) -> HashMap<(NodeIndex, NodeIndex), usize> {
+    let mut dists = HashMap::new();
+
+    for n in graph.node_indices() {
+        let d = dijkstra(&graph, n, None, |e| *e.weight());
+        for (k, v) in d.iter() {
+            dists.insert((n, *k), *v);
+        }
+    }
+
+    dists
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    let (graph, start, _) = parse(input)?;
+    let dists = distances(&graph);
+
+    Ok(part_1(&graph, &dists, start)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    let (graph, start, _) = parse(input)?;
+    let dists = distances(&graph);
+
+    Ok(part_2(&graph, &dists, start)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use proptest::prelude::*;
+    use std::collections::VecDeque;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(1651));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(1707));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    /// Minutes from `AA` to each valve.
+    fn minutes_from_start(valves: &[Valve]) -> HashMap<&str, usize> {
+        let tunnels = valves
+            .iter()
+            .map(|v| (v.id.as_str(), &v.tunnel_to))
+            .collect::<HashMap<_, _>>();
+
+        let mut minutes = HashMap::from([("AA", 0)]);
+        let mut queue = VecDeque::from(["AA"]);
+        while let Some(id) = queue.pop_front() {
+            let next = minutes[id] + 1;
+            for to in tunnels[id] {
+                if !minutes.contains_key(to.as_str()) {
+                    minutes.insert(to, next);
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        minutes
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn keeps_only_valves_with_flow(valves in gen::valves()) {
+            let (graph, start, _) = parse(&gen::to_input(&valves)).unwrap();
+
+            let mut kept = graph
+                .node_weights()
+                .filter(|(id, _)| id != "AA")
+                .cloned()
+                .collect::<Vec<_>>();
+            kept.sort();
+            let mut with_flow = valves
+                .iter()
+                .filter(|v| v.flow > 0)
+                .map(|v| (v.id.clone(), v.flow))
+                .collect::<Vec<_>>();
+            with_flow.sort();
+
+            prop_assert_eq!(&graph[start].0, "AA");
+            prop_assert_eq!(kept, with_flow);
+        }
+
+        #[test]
+        fn beats_opening_a_single_valve(valves in gen::valves()) {
+            let input = gen::to_input(&valves);
+            let minutes = minutes_from_start(&valves);
+
+            let best_single = |time: usize| {
+                valves
+                    .iter()
+                    .map(|v| time.saturating_sub(minutes[v.id.as_str()] + 1) * v.flow)
+                    .max()
+                    .unwrap_or(0)
+            };
+            let at_most = |time: usize| valves.iter().map(|v| v.flow).sum::<usize>() * (time - 2);
+
+            for (part, time) in [(1, 30), (2, 26)] {
+                let solve = if part == 1 { solve_part1 } else { solve_part2 };
+                let Answer::Number(released) = solve(&input).unwrap() else {
+                    panic!("Part {} is not a number", part);
+                };
+
+                prop_assert!(released as usize >= best_single(time));
+                prop_assert!(released as usize <= at_most(time));
+            }
+        }
+    }
+
+    #[test]
+    fn reference_example() {
+        assert_eq!(reference::part_1(EXAMPLE).unwrap(), 1651);
+        assert_eq!(reference::part_2(EXAMPLE).unwrap(), 1707);
+    }
+}
@@ -0,0 +1,105 @@
+//! Exhaustive search over the scan as given, to check the graph
+//! simplification and the search of `part_2` with.
+
+use crate::parse_valves;
+use anyhow::{bail, Error as AnyhowError};
+use std::collections::{HashMap, VecDeque};
+
+/// Minutes from `from` to every valve it can reach.
+fn minutes_from<'a>(
+    tunnels: &HashMap<&'a str, Vec<&'a str>>,
+    from: &'a str,
+) -> HashMap<&'a str, usize> {
+    let mut minutes = HashMap::from([(from, 0)]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(id) = queue.pop_front() {
+        let next = minutes[id] + 1;
+        for &to in tunnels.get(id).into_iter().flatten() {
+            if !minutes.contains_key(to) {
+                minutes.insert(to, next);
+                queue.push_back(to);
+            }
+        }
+    }
+
+    minutes
+}
+
+/// Most pressure released by opening each set of valves with flow, as a
+/// bitmask, in some order within `time` minutes from `AA`.
+fn best_by_opened(input: &str, time: usize) -> Result<HashMap<u64, usize>, AnyhowError> {
+    let valves = parse_valves(input)?;
+    let tunnels = valves
+        .iter()
+        .map(|v| {
+            (
+                v.id.as_str(),
+                v.tunnel_to.iter().map(|t| t.as_str()).collect(),
+            )
+        })
+        .collect::<HashMap<_, Vec<_>>>();
+
+    let useful = valves.iter().filter(|v| v.flow > 0).collect::<Vec<_>>();
+    if useful.len() > 64 {
+        bail!("Too many valves with flow: {}", useful.len());
+    }
+
+    let start = minutes_from(&tunnels, "AA");
+    let between = useful
+        .iter()
+        .map(|v| minutes_from(&tunnels, &v.id))
+        .collect::<Vec<_>>();
+
+    let mut best = HashMap::new();
+    let mut stack = useful
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| Some((i, time.checked_sub(start.get(v.id.as_str())? + 1)?, 0u64, 0)))
+        .collect::<Vec<_>>();
+    best.insert(0, 0);
+
+    while let Some((at, time_left, opened, released)) = stack.pop() {
+        let opened = opened | 1 << at;
+        let released = released + time_left * useful[at].flow;
+
+        let entry = best.entry(opened).or_insert(0);
+        *entry = (*entry).max(released);
+
+        for (next, v) in useful.iter().enumerate() {
+            if opened & 1 << next != 0 {
+                continue;
+            }
+            let Some(minutes) = between[at].get(v.id.as_str()) else {
+                continue;
+            };
+            if let Some(left) = time_left.checked_sub(minutes + 1) {
+                stack.push((next, left, opened, released));
+            }
+        }
+    }
+
+    Ok(best)
+}
+
+pub fn part_1(input: &str) -> Result<usize, AnyhowError> {
+    let best = best_by_opened(input, 30)?;
+
+    Ok(best.values().copied().max().unwrap_or(0))
+}
+
+/// Tries every split of the valves between you and the elephant.
+pub fn part_2(input: &str) -> Result<usize, AnyhowError> {
+    let best = best_by_opened(input, 26)?;
+
+    let mut most = 0;
+    for (&yours, &you) in &best {
+        for (&elephants, &elephant) in &best {
+            if yours & elephants == 0 {
+                most = most.max(you + elephant);
+            }
+        }
+    }
+
+    Ok(most)
+}
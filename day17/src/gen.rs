@@ -0,0 +1,8 @@
+//! Random jet patterns.
+
+use proptest::prelude::*;
+
+/// A short jet pattern, so that the tower soon turns periodic.
+pub fn input() -> impl Strategy<Value = String> {
+    "[<>]{1,40}".prop_map(|jets| jets + "\n")
+}
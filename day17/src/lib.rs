@@ -0,0 +1,385 @@
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{
+    debug,
+    grid::Pos,
+    parse::parse_lines,
+    visual::{frame_from_rows, Frame, Palette, Recorder},
+    Answer, Grid, LineError,
+};
+use std::io;
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+#[cfg(any(test, feature = "reference"))]
+pub mod reference;
+
+static ROCKS: &[&[&[u8]]] = &[
+    &[&[1, 1, 1, 1]],
+    &[&[0, 1, 0], &[1, 1, 1], &[0, 1, 0]],
+    &[&[0, 0, 1], &[0, 0, 1], &[1, 1, 1]],
+    &[&[1], &[1], &[1], &[1]],
+    &[&[1, 1], &[1, 1]],
+];
+
+/// Width of the chamber the rocks fall into.
+const WIDTH: usize = 7;
+
+/// Grid position of the cell `left` units from the left wall and `height`
+/// units above the floor.
+fn at(left: usize, height: usize) -> Pos {
+    (left as isize, height as isize)
+}
+
+/// The field with the falling rock drawn in as 2s.
+fn with_rock(field: &Grid<u8>, rock_idx: usize, height: usize, left: usize) -> Grid<u8> {
+    let mut field = field.clone();
+
+    let rock = ROCKS[rock_idx];
+
+    for j in 0..rock.len() {
+        for i in 0..rock[j].len() {
+            if field[at(left + i, height + j)] == 0 {
+                field[at(left + i, height + j)] = 2 * rock[rock.len() - j - 1][i];
+            }
+        }
+    }
+
+    field
+}
+
+fn cell(v: u8) -> char {
+    match v {
+        0 => '.',
+        1 => '#',
+        2 => '@',
+        _ => ' ',
+    }
+}
+
+/// Rows shown when drawing the top of the tower.
+const VIEW: usize = 40;
+
+fn tower_frame(field: &Grid<u8>, rock_idx: usize, height: usize, left: usize) -> Frame {
+    let field = with_rock(field, rock_idx, height, left);
+    let rows = field.rows().collect::<Vec<_>>();
+
+    frame_from_rows(
+        rows.iter()
+            .rev()
+            .take(VIEW)
+            .map(|r| r.iter().map(|&v| cell(v))),
+    )
+}
+
+fn print_field(
+    field: &Grid<u8>,
+    rock_idx: usize,
+    height: usize,
+    left: usize,
+    stop_after: bool,
+) -> Result<(), AnyhowError> {
+    let field = with_rock(field, rock_idx, height, left);
+    let rows = field.render(|&v| cell(v));
+
+    eprintln!();
+    for row in rows.iter().rev().take(30) {
+        eprintln!("|{}|", row);
+    }
+    if field.height() > 20 {
+        eprintln!("~~~~~~~~~");
+    } else {
+        eprintln!("+-------+");
+    }
+    eprintln!();
+
+    if stop_after {
+        eprintln!(
+            "Rock {} at height {}, field of height {}",
+            rock_idx,
+            height,
+            field.height()
+        );
+        io::stdin().read_line(&mut "".to_owned())?;
+    }
+
+    Ok(())
+}
+
+fn will_collide_at(rock_idx: usize, left: &usize, height: &usize, field: &Grid<u8>) -> bool {
+    let rock = ROCKS[rock_idx];
+
+    let mut collided = false;
+
+    'outer: for j in 0..rock.len() {
+        for i in 0..rock[j].len() {
+            if field[at(*left + i, *height + j)] != 0 && rock[rock.len() - j - 1][i] != 0 {
+                collided = true;
+                break 'outer;
+            }
+        }
+    }
+
+    collided
+}
+
+fn move_rock(
+    rock_idx: usize,
+    jets: &mut dyn Iterator<Item = &char>,
+    left: &mut usize,
+    height: &mut usize,
+    field: &Grid<u8>,
+    bottom: usize,
+    debug: bool,
+) -> Result<bool, AnyhowError> {
+    let jet = jets.next().ok_or(anyhow!("No jet!"))?;
+    let rock = ROCKS[rock_idx];
+
+    match jet {
+        '>' => {
+            if (*left + rock[0].len() < WIDTH)
+                && !will_collide_at(rock_idx, &(*left + 1), height, field)
+            {
+                *left += 1;
+            }
+        }
+        '<' => {
+            if *left > 0 && !will_collide_at(rock_idx, &(*left - 1), height, field) {
+                *left -= 1;
+            }
+        }
+        v => bail!("Unknown jet direction {}", v),
+    };
+
+    let will_collide_down = *height == 0
+        || (*height <= bottom && will_collide_at(rock_idx, left, &(*height - 1), field));
+
+    if debug {
+        eprintln!("Move {}", jet);
+        print_field(field, rock_idx, *height, *left, true)?;
+    }
+
+    if !will_collide_down {
+        *height -= 1;
+
+        if debug {
+            eprintln!("Move down");
+            print_field(field, rock_idx, *height, *left, true)?;
+        }
+    }
+
+    Ok(will_collide_down)
+}
+
+fn stop_rock(rock_idx: usize, left: &mut usize, height: &mut usize, field: &mut Grid<u8>) -> usize {
+    let rock = ROCKS[rock_idx];
+
+    for j in 0..rock.len() {
+        for i in 0..rock[j].len() {
+            field[at(*left + i, *height + j)] |= rock[rock.len() - j - 1][i];
+        }
+    }
+
+    rock.len()
+}
+
+/// Parses the jet pattern, a single line of `<` and `>`.
+pub fn parse(input: &str) -> Result<Vec<char>, AnyhowError> {
+    parse_lines(input, |s| {
+        match s.char_indices().find(|(_, c)| !matches!(c, '<' | '>')) {
+            Some((i, _)) => Err(LineError::at(s, &s[i..], "< or >")),
+            None => Ok(s.chars().collect::<Vec<_>>()),
+        }
+    })?
+    .into_iter()
+    .next()
+    .ok_or(anyhow!("No input!"))
+}
+
+fn rock_simulator(
+    input: &str,
+    part: u8,
+    stone_count: usize,
+    debug: bool,
+) -> Result<Vec<usize>, AnyhowError> {
+    let gas_jets = parse(input)?;
+
+    let mut additions = vec![];
+
+    let mut field = Grid::new(WIDTH, 4, 0u8);
+
+    let mut rock_idx: usize = 0;
+    let mut bottom = 0;
+    let mut height = 3;
+    let mut left = 2;
+    let mut jets = gas_jets.iter().cycle();
+
+    let mut rocks_stopped = 0;
+
+    let palette = Palette::new([20, 20, 25])
+        .with('.', [35, 35, 45])
+        .with('#', [150, 150, 160])
+        .with('@', [240, 120, 40]);
+    let mut recorder = Recorder::for_part(17, part, palette)
+        .with_scale(8)
+        .with_sampling(stone_count / 400);
+
+    if debug {
+        print_field(&field, rock_idx, height, left, true)?;
+    }
+
+    while rocks_stopped < stone_count {
+        loop {
+            let will_collide = move_rock(
+                rock_idx,
+                &mut jets,
+                &mut left,
+                &mut height,
+                &field,
+                bottom,
+                debug,
+            )?;
+
+            if will_collide {
+                break;
+            }
+        }
+
+        let height_added = stop_rock(rock_idx, &mut left, &mut height, &mut field);
+        rocks_stopped += 1;
+
+        rock_idx += 1;
+        if rock_idx > ROCKS.len() - 1 {
+            rock_idx = 0;
+        }
+
+        let new_bottom = height + height_added;
+
+        if new_bottom > bottom {
+            additions.push(new_bottom - bottom);
+            bottom = new_bottom;
+        } else {
+            additions.push(0);
+        }
+
+        height = 3 + bottom;
+        left = 2;
+
+        let rock = ROCKS[rock_idx];
+        while field.height() < height + rock.len() {
+            field.push_row(0);
+        }
+
+        recorder.step(|| tower_frame(&field, rock_idx, height, left));
+
+        if debug {
+            print_field(&field, rock_idx, height, left, true)?;
+        }
+    }
+
+    recorder.last(|| tower_frame(&field, rock_idx, height, left));
+    recorder.finish()?;
+
+    debug!("Rocks stopped: {}", rocks_stopped);
+    debug!("Tower height: {}", bottom);
+    debug!(
+        "{} additions of {} in total",
+        additions.len(),
+        additions.iter().sum::<usize>()
+    );
+
+    Ok(additions)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    let v = rock_simulator(input, 1, 2022, false)?;
+
+    Ok(v.iter().sum::<usize>().into())
+}
+
+/// Height of the tower after `rocks` rocks, extrapolated from the period of
+/// the heights the first 10000 add.
+pub fn tower_height(input: &str, rocks: usize) -> Result<usize, AnyhowError> {
+    let stone_count = 10_000;
+
+    let v = rock_simulator(input, 2, stone_count, false)?;
+    if rocks <= v.len() {
+        return Ok(v[..rocks].iter().sum());
+    }
+
+    let mut period = None;
+    'outer: for start in (v.len() / 2)..v.len() {
+        let orig = v[start..].to_vec();
+
+        for i in 1..(v.len() - start) {
+            let mut rot = orig.clone();
+            rot.rotate_left(i);
+
+            if orig.eq(&rot) {
+                period = Some((start, i));
+                break 'outer;
+            }
+        }
+    }
+
+    let (start, period) = period.ok_or(anyhow!("No period!"))?;
+    debug!("Period of {} from rock {}", period, start);
+
+    let prefix_height: usize = v[0..start].iter().sum();
+
+    let cycle_body = v[start..start + period].to_owned();
+    let height_per_cycle = cycle_body.iter().sum::<usize>();
+
+    let stones_to_compute = rocks - start;
+
+    let cycle_count: usize = stones_to_compute / period;
+    let cycle_total_height = cycle_count * height_per_cycle;
+    let target_height = prefix_height + cycle_total_height;
+
+    let stones_remaining = stones_to_compute - cycle_count * period;
+    let partial_cycle_height: usize = cycle_body[0..stones_remaining].iter().sum();
+
+    debug!(
+        "{} rocks: {} cycles of height {} up to {}, then {} rocks of height {}",
+        rocks, cycle_count, height_per_cycle, target_height, stones_remaining, partial_cycle_height,
+    );
+
+    Ok(target_height + partial_cycle_height)
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(tower_height(input, 1_000_000_000_000)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(3068));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(1514285714288));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    #[ignore = "slow: simulates rocks until a period shows up"]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    #[test]
+    fn reference_example() {
+        assert_eq!(reference::tower_height(EXAMPLE, 2022).unwrap(), 3068);
+    }
+}
@@ -0,0 +1,61 @@
+//! Rock by rock simulation, to check the period detection of
+//! `tower_height` with.
+
+use crate::{parse, ROCKS, WIDTH};
+use anyhow::Error as AnyhowError;
+use std::collections::HashSet;
+
+/// Cells of a rock as offsets from its bottom left corner, y going up.
+fn cells(rock: &[&[u8]]) -> Vec<(isize, isize)> {
+    let mut cells = vec![];
+    for (j, row) in rock.iter().enumerate() {
+        for (i, &c) in row.iter().enumerate() {
+            if c != 0 {
+                cells.push((i as isize, (rock.len() - 1 - j) as isize));
+            }
+        }
+    }
+    cells
+}
+
+/// Height of the tower after dropping all `rocks` rocks one by one.
+pub fn tower_height(input: &str, rocks: usize) -> Result<usize, AnyhowError> {
+    let jets = parse(input)?;
+
+    let mut filled = HashSet::new();
+    let mut top = 0;
+    let mut jet = 0;
+
+    for r in 0..rocks {
+        let rock = cells(ROCKS[r % ROCKS.len()]);
+        let (mut x, mut y) = (2, top + 3);
+
+        let fits = |x: isize, y: isize| {
+            rock.iter().all(|&(dx, dy)| {
+                let (cx, cy) = (x + dx, y + dy);
+                (0..WIDTH as isize).contains(&cx) && cy >= 0 && !filled.contains(&(cx, cy))
+            })
+        };
+
+        loop {
+            let dx = if jets[jet % jets.len()] == '<' { -1 } else { 1 };
+            jet += 1;
+
+            if fits(x + dx, y) {
+                x += dx;
+            }
+            if fits(x, y - 1) {
+                y -= 1;
+            } else {
+                break;
+            }
+        }
+
+        for (dx, dy) in rock {
+            filled.insert((x + dx, y + dy));
+            top = top.max(y + dy + 1);
+        }
+    }
+
+    Ok(top as usize)
+}
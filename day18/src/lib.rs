@@ -0,0 +1,107 @@
+use anyhow::Error as AnyhowError;
+use aoc_common::{
+    grid::{neighbors6, Voxel},
+    parse::parse_lines,
+    Answer, Grid3, LineError,
+};
+
+fn flood_fill(it: &mut Grid3<u8>, start: Voxel, kind: u8) {
+    let mut stack = vec![start];
+
+    while let Some(voxel) = stack.pop() {
+        if it[voxel] != 0 {
+            continue;
+        }
+        it[voxel] = kind;
+
+        stack.extend(it.neighbors6(voxel));
+    }
+}
+
+/// Side of the cube every droplet coordinate has to fit in.
+const SIZE: usize = 32;
+
+/// Parses the scan into the positions of the lava cubes.
+pub fn parse(input: &str) -> Result<Vec<(usize, usize, usize)>, AnyhowError> {
+    Ok(parse_lines(input, |s| {
+        let coords = s
+            .split(',')
+            .map(|c| match c.parse::<usize>() {
+                Ok(v) if v < SIZE => Ok(v),
+                _ => Err(LineError::at(
+                    s,
+                    c,
+                    format!("a coordinate from 0 to {}", SIZE - 1),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match coords[..] {
+            [x, y, z] => Ok((x, y, z)),
+            _ => Err(LineError::new(1, "three comma-separated coordinates")),
+        }
+    })?)
+}
+
+/// Counts lava cube faces touching air, or with `exterior_only` just the ones
+/// reachable by water from outside the droplet.
+fn surface_area(input: &str, exterior_only: bool) -> Result<usize, AnyhowError> {
+    let mut droplet = Grid3::new(SIZE, SIZE, SIZE, 0u8);
+    for (x, y, z) in parse(input)? {
+        droplet[(x as isize, y as isize, z as isize)] = 1u8;
+    }
+
+    let kind = if exterior_only {
+        let corner = SIZE as isize - 1;
+        flood_fill(&mut droplet, (corner, corner, corner), 2);
+        2
+    } else {
+        0
+    };
+
+    // Faces on the border of the grid always count, as nothing lies beyond.
+    let surface = droplet
+        .iter()
+        .filter(|&(_, &me)| me == 1)
+        .flat_map(|(voxel, _)| neighbors6(voxel))
+        .filter(|&n| droplet.get(n).is_none_or(|&c| c == kind))
+        .count();
+
+    Ok(surface)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(surface_area(input, false)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(surface_area(input, true)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(64));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(58));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+}
@@ -0,0 +1,403 @@
+//! Games of the Rock-Paper-Scissors family, defined by their moves in order,
+//! which move beats which, and how many points moves and outcomes are worth.
+//!
+//! A definition has one statement per line, `#` starting a comment:
+//!
+//! ```text
+//! # name  their code  my code  points
+//! move Rock     A X 1
+//! move Paper    B Y 2
+//! move Scissors C Z 3
+//! # outcome  code  points
+//! outcome lose X 0
+//! outcome draw Y 3
+//! outcome win  Z 6
+//! ```
+//!
+//! Without any `beats <move> <move>...` statement, every move beats the
+//! `(n - 1) / 2` moves listed before it, cycling around the list.
+
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{parse::parse_numbered, LineError};
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    str::FromStr,
+};
+
+/// The game of the puzzle.
+const ROCK_PAPER_SCISSORS: &str = "\
+move Rock     A X 1
+move Paper    B Y 2
+move Scissors C Z 3
+outcome lose X 0
+outcome draw Y 3
+outcome win  Z 6
+";
+
+/// A move, as its index in the game's list of moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Figure(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+/// A code of the second column of the strategy guide, whose meaning is up
+/// to the part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Code(pub char);
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Outcome::Win => "Win",
+            Outcome::Lose => "Lose",
+            Outcome::Draw => "Draw",
+        })
+    }
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.0.encode_utf8(&mut [0; 4]))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Move {
+    name: String,
+    theirs: char,
+    mine: char,
+    points: u32,
+}
+
+/// A statement of a game definition.
+enum Statement {
+    Move(Move),
+    Outcome(Outcome, char, u32),
+    Beats(String, Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    moves: Vec<Move>,
+    /// Code and points of each outcome, indexed like `Outcome::ALL`.
+    outcomes: [(char, u32); 3],
+    /// `beats[a][b]` if move `a` beats move `b`.
+    beats: Vec<Vec<bool>>,
+}
+
+fn code(line: &str, s: &str) -> Result<char, LineError> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(LineError::at(line, s, "a single character code")),
+    }
+}
+
+fn points(line: &str, s: &str) -> Result<u32, LineError> {
+    s.parse()
+        .map_err(|_| LineError::at(line, s, "a number of points"))
+}
+
+fn statement(line: &str) -> Result<Statement, LineError> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+
+    match words[..] {
+        ["move", name, theirs, mine, p] => Ok(Statement::Move(Move {
+            name: name.to_owned(),
+            theirs: code(line, theirs)?,
+            mine: code(line, mine)?,
+            points: points(line, p)?,
+        })),
+        ["outcome", outcome, c, p] => {
+            let outcome = match outcome {
+                "lose" => Outcome::Lose,
+                "draw" => Outcome::Draw,
+                "win" => Outcome::Win,
+                o => return Err(LineError::at(line, o, "lose, draw or win")),
+            };
+            Ok(Statement::Outcome(
+                outcome,
+                code(line, c)?,
+                points(line, p)?,
+            ))
+        }
+        ["beats", winner, ref losers @ ..] if !losers.is_empty() => Ok(Statement::Beats(
+            winner.to_owned(),
+            losers.iter().map(|&l| l.to_owned()).collect(),
+        )),
+        _ => Err(LineError::new(
+            1,
+            "move <name> <code> <code> <points>, outcome <lose|draw|win> <code> <points> \
+             or beats <move> <move>...",
+        )),
+    }
+}
+
+impl FromStr for Game {
+    type Err = AnyhowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s
+            .lines()
+            .map(|l| l.split_once('#').map_or(l, |(l, _)| l))
+            .enumerate();
+
+        let mut moves: Vec<Move> = vec![];
+        let mut outcomes = [None; 3];
+        let mut beats_stated = vec![];
+        for statement in parse_numbered(lines, statement)? {
+            match statement {
+                Statement::Move(m) => {
+                    if moves.iter().any(|o| o.name == m.name) {
+                        bail!("Move {} is defined twice", m.name);
+                    }
+                    if moves
+                        .iter()
+                        .any(|o| o.theirs == m.theirs || o.mine == m.mine)
+                    {
+                        bail!("Codes of move {} are already taken", m.name);
+                    }
+                    moves.push(m);
+                }
+                Statement::Outcome(o, c, p) => outcomes[o as usize] = Some((c, p)),
+                Statement::Beats(winner, losers) => beats_stated.push((winner, losers)),
+            }
+        }
+
+        let n = moves.len();
+        if n < 3 || n.is_multiple_of(2) {
+            bail!("A game needs an odd number of moves, at least 3, not {}", n);
+        }
+        let outcomes = outcomes
+            .iter()
+            .zip(Outcome::ALL)
+            .map(|(o, outcome)| o.ok_or(anyhow!("Outcome {} is not defined", outcome)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outcomes = [outcomes[0], outcomes[1], outcomes[2]];
+        if outcomes[0].0 == outcomes[1].0
+            || outcomes[1].0 == outcomes[2].0
+            || outcomes[0].0 == outcomes[2].0
+        {
+            bail!("Outcomes need codes of their own");
+        }
+
+        let index = |name: &str| {
+            moves
+                .iter()
+                .position(|m| m.name == name)
+                .ok_or(anyhow!("Unknown move {}", name))
+        };
+        let mut beats = vec![vec![false; n]; n];
+        if beats_stated.is_empty() {
+            for (a, row) in beats.iter_mut().enumerate() {
+                for d in 1..=(n - 1) / 2 {
+                    row[(a + n - d) % n] = true;
+                }
+            }
+        }
+        for (winner, losers) in &beats_stated {
+            let a = index(winner)?;
+            for loser in losers {
+                beats[a][index(loser)?] = true;
+            }
+        }
+
+        for a in 0..n {
+            if beats[a][a] {
+                bail!("Move {} beats itself", moves[a].name);
+            }
+            for b in a + 1..n {
+                if beats[a][b] == beats[b][a] {
+                    bail!(
+                        "Exactly one of {} and {} must beat the other",
+                        moves[a].name,
+                        moves[b].name
+                    );
+                }
+            }
+            if !beats[a].contains(&true) || !(0..n).any(|b| beats[b][a]) {
+                bail!(
+                    "Move {} must beat some move and lose to another",
+                    moves[a].name
+                );
+            }
+        }
+
+        Ok(Game {
+            moves,
+            outcomes,
+            beats,
+        })
+    }
+}
+
+impl Game {
+    /// Plain Rock-Paper-Scissors, as in the puzzle.
+    pub fn rock_paper_scissors() -> Self {
+        ROCK_PAPER_SCISSORS.parse().expect("valid game definition")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AnyhowError> {
+        let path = path.as_ref();
+        let definition = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Can't read {}: {}", path.display(), e))?;
+
+        definition
+            .parse()
+            .map_err(|e: AnyhowError| anyhow!("Bad game {}: {}", path.display(), e))
+    }
+
+    pub fn figures(&self) -> impl Iterator<Item = Figure> {
+        (0..self.moves.len()).map(Figure)
+    }
+
+    pub fn figure(&self, name: &str) -> Option<Figure> {
+        self.moves.iter().position(|m| m.name == name).map(Figure)
+    }
+
+    pub fn name(&self, figure: Figure) -> &str {
+        &self.moves[figure.0].name
+    }
+
+    /// The figure an opponent's code stands for.
+    pub fn their_figure(&self, code: char) -> Option<Figure> {
+        self.moves.iter().position(|m| m.theirs == code).map(Figure)
+    }
+
+    /// The figure to play, when codes stand for figures.
+    pub fn my_figure(&self, code: Code) -> Option<Figure> {
+        self.moves.iter().position(|m| m.mine == code.0).map(Figure)
+    }
+
+    /// The outcome to aim for, when codes stand for outcomes.
+    pub fn outcome_of(&self, code: Code) -> Option<Outcome> {
+        Outcome::ALL
+            .into_iter()
+            .zip(self.outcomes)
+            .find(|&(_, (c, _))| c == code.0)
+            .map(|(o, _)| o)
+    }
+
+    /// The code standing for `figure`, when codes stand for figures.
+    pub fn my_code(&self, figure: Figure) -> Code {
+        Code(self.moves[figure.0].mine)
+    }
+
+    /// The code standing for `outcome`, when codes stand for outcomes.
+    pub fn outcome_code(&self, outcome: Outcome) -> Code {
+        Code(self.outcomes[outcome as usize].0)
+    }
+
+    /// Codes of the second column, as figures then as outcomes.
+    pub fn codes(&self) -> impl Iterator<Item = Code> + '_ {
+        let mine = self.moves.iter().map(|m| m.mine);
+        mine.chain(self.outcomes.iter().map(|&(c, _)| c)).map(Code)
+    }
+
+    pub fn figure_points(&self, figure: Figure) -> u32 {
+        self.moves[figure.0].points
+    }
+
+    pub fn outcome_points(&self, outcome: Outcome) -> u32 {
+        self.outcomes[outcome as usize].1
+    }
+
+    pub fn outcome(&self, my: Figure, theirs: Figure) -> Outcome {
+        if my == theirs {
+            Outcome::Draw
+        } else if self.beats[my.0][theirs.0] {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    /// The figure leading to `outcome` against `theirs`, the first one in the
+    /// list of moves if several do.
+    pub fn inv_outcome(&self, outcome: Outcome, theirs: Figure) -> Figure {
+        self.figures()
+            .find(|&my| self.outcome(my, theirs) == outcome)
+            .expect("every move beats some move and loses to another")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn games_dir() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("games")
+    }
+
+    #[test]
+    fn plays_rock_paper_scissors() {
+        let game = Game::rock_paper_scissors();
+        let [rock, paper, scissors] =
+            ["Rock", "Paper", "Scissors"].map(|name| game.figure(name).unwrap());
+
+        assert_eq!(game.outcome(paper, rock), Outcome::Win);
+        assert_eq!(game.outcome(rock, paper), Outcome::Lose);
+        assert_eq!(game.outcome(scissors, scissors), Outcome::Draw);
+        assert_eq!(game.inv_outcome(Outcome::Win, scissors), rock);
+        assert_eq!(game.inv_outcome(Outcome::Lose, scissors), paper);
+        assert_eq!(game.figure_points(scissors), 3);
+        assert_eq!(game.outcome_points(Outcome::Win), 6);
+    }
+
+    #[test]
+    fn loads_bigger_games() {
+        let game = Game::load(games_dir().join("rpsls.txt")).unwrap();
+        let figure = |name| game.figure(name).unwrap();
+
+        assert_eq!(game.outcome(figure("Spock"), figure("Rock")), Outcome::Win);
+        assert_eq!(
+            game.outcome(figure("Lizard"), figure("Spock")),
+            Outcome::Win
+        );
+        assert_eq!(
+            game.outcome(figure("Lizard"), figure("Rock")),
+            Outcome::Lose
+        );
+        assert_eq!(
+            game.inv_outcome(Outcome::Win, figure("Scissors")),
+            figure("Rock")
+        );
+
+        let game = Game::load(games_dir().join("rps7.txt")).unwrap();
+        let figure = |name| game.figure(name).unwrap();
+        for loser in ["Fire", "Scissors", "Sponge"] {
+            assert_eq!(game.outcome(figure("Rock"), figure(loser)), Outcome::Win);
+        }
+        for winner in ["Paper", "Air", "Water"] {
+            assert_eq!(game.outcome(figure("Rock"), figure(winner)), Outcome::Lose);
+        }
+    }
+
+    #[test]
+    fn rejects_unfair_games() {
+        let moves = "move R A X 1\nmove P B Y 2\nmove S C Z 3\n";
+        let outcomes = "outcome lose X 0\noutcome draw Y 3\noutcome win Z 6\n";
+        let game = |extra: &str| format!("{}{}{}", moves, outcomes, extra).parse::<Game>();
+
+        assert!(game("").is_ok());
+        assert!(game("beats P R\nbeats S P\nbeats R S\n").is_ok());
+        assert!(game("beats P R S\nbeats S R\n").is_err());
+        assert!(game("beats P R\nbeats S P\n").is_err());
+        assert!(game("move L D W 4\n").is_err());
+        assert!(format!("{}outcome win Z 6\n", moves)
+            .parse::<Game>()
+            .is_err());
+        assert!(game("move Q A V 4\nmove T E U 5\n").is_err());
+    }
+}
@@ -0,0 +1,278 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::{parse::parse_lines, Answer, LineError};
+use game::{Code, Figure, Game, Outcome};
+use std::fmt::{self, Display, Write};
+
+pub mod game;
+pub mod optimize;
+pub mod tournament;
+
+/// Parses the strategy guide into `<their figure> <code>` rounds of `game`.
+pub fn parse_guide(game: &Game, input: &str) -> Result<Vec<(Figure, Code)>, AnyhowError> {
+    Ok(parse_lines(input, |line| {
+        let (l, r) = line
+            .split_once(' ')
+            .ok_or(LineError::new(1, "two codes separated by a space"))?;
+
+        let single = |s: &str| {
+            let mut chars = s.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        };
+        let theirs = single(l)
+            .and_then(|c| game.their_figure(c))
+            .ok_or(LineError::at(line, l, "an opponent's code"))?;
+        let code = single(r)
+            .map(Code)
+            .filter(|&c| game.codes().any(|known| known == c))
+            .ok_or(LineError::at(line, r, "a code of the second column"))?;
+
+        Ok((theirs, code))
+    })?)
+}
+
+/// Parses a Rock-Paper-Scissors strategy guide.
+pub fn parse(input: &str) -> Result<Vec<(Figure, Code)>, AnyhowError> {
+    parse_guide(&Game::rock_paper_scissors(), input)
+}
+
+/// How the second column of the strategy guide is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    /// The codes are the figure to play, as in part 1.
+    Figure,
+    /// The codes are the outcome to aim for, as in part 2.
+    Outcome,
+}
+
+impl Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reading::Figure => write!(f, "codes are the figure to play"),
+            Reading::Outcome => write!(f, "codes are the outcome to aim for"),
+        }
+    }
+}
+
+/// A round of the strategy guide, as played under some reading of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub theirs: Figure,
+    pub code: Code,
+    pub my: Figure,
+    pub outcome: Outcome,
+    pub figure_points: u32,
+    pub outcome_points: u32,
+}
+
+impl Round {
+    pub fn play(
+        game: &Game,
+        theirs: Figure,
+        code: Code,
+        reading: Reading,
+    ) -> Result<Self, AnyhowError> {
+        let (my, outcome) = match reading {
+            Reading::Figure => {
+                let my = game
+                    .my_figure(code)
+                    .ok_or(anyhow!("Code {} stands for no figure", code))?;
+                (my, game.outcome(my, theirs))
+            }
+            Reading::Outcome => {
+                let outcome = game
+                    .outcome_of(code)
+                    .ok_or(anyhow!("Code {} stands for no outcome", code))?;
+                (game.inv_outcome(outcome, theirs), outcome)
+            }
+        };
+
+        Ok(Round {
+            theirs,
+            code,
+            my,
+            outcome,
+            figure_points: game.figure_points(my),
+            outcome_points: game.outcome_points(outcome),
+        })
+    }
+
+    pub fn score(&self) -> u32 {
+        self.figure_points + self.outcome_points
+    }
+}
+
+/// Plays every round of the guide under the given reading.
+pub fn play(
+    game: &Game,
+    guide: &[(Figure, Code)],
+    reading: Reading,
+) -> Result<Vec<Round>, AnyhowError> {
+    guide
+        .iter()
+        .map(|&(theirs, code)| Round::play(game, theirs, code, reading))
+        .collect()
+}
+
+/// One line per round with the figures, the outcome and where the score
+/// comes from, along with the running total.
+pub fn breakdown(game: &Game, rounds: &[Round]) -> String {
+    let width = game
+        .figures()
+        .map(|f| game.name(f).len())
+        .max()
+        .unwrap_or(0);
+    let mut out = format!(
+        "{:>5}  {:<w$}  {:<4}  {:<w$}  {:<7}  {:>6}  {:>7}  {:>5}  {:>6}\n",
+        "round",
+        "theirs",
+        "code",
+        "mine",
+        "result",
+        "figure",
+        "outcome",
+        "score",
+        "total",
+        w = width.max(6)
+    );
+
+    let mut total = 0;
+    for (i, r) in rounds.iter().enumerate() {
+        total += r.score();
+        writeln!(
+            out,
+            "{:>5}  {:<w$}  {:<4}  {:<w$}  {:<7}  {:>6}  {:>7}  {:>5}  {:>6}",
+            i + 1,
+            game.name(r.theirs),
+            r.code,
+            game.name(r.my),
+            r.outcome,
+            r.figure_points,
+            r.outcome_points,
+            r.score(),
+            total,
+            w = width.max(6)
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+/// Total score of a guide of `game` under the given reading.
+pub fn total_score(game: &Game, input: &str, reading: Reading) -> Result<u32, AnyhowError> {
+    let rounds = play(game, &parse_guide(game, input)?, reading)?;
+    Ok(rounds.iter().map(Round::score).sum())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(total_score(&Game::rock_paper_scissors(), input, Reading::Figure)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(total_score(&Game::rock_paper_scissors(), input, Reading::Outcome)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use std::path::Path;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(15));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(12));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    #[test]
+    fn plays_both_readings() {
+        let game = Game::rock_paper_scissors();
+        let guide = parse(EXAMPLE).unwrap();
+        let [rock, paper] = ["Rock", "Paper"].map(|name| game.figure(name).unwrap());
+
+        let rounds = play(&game, &guide, Reading::Figure).unwrap();
+        assert_eq!(
+            rounds[0],
+            Round {
+                theirs: rock,
+                code: Code('Y'),
+                my: paper,
+                outcome: Outcome::Win,
+                figure_points: 2,
+                outcome_points: 6,
+            }
+        );
+        assert_eq!(
+            rounds.iter().map(Round::score).collect::<Vec<_>>(),
+            [8, 1, 6]
+        );
+
+        let rounds = play(&game, &guide, Reading::Outcome).unwrap();
+        assert_eq!((rounds[1].my, rounds[1].outcome), (rock, Outcome::Lose));
+        assert_eq!(
+            rounds.iter().map(Round::score).collect::<Vec<_>>(),
+            [4, 1, 7]
+        );
+    }
+
+    #[test]
+    fn breaks_down_each_round() {
+        let game = Game::rock_paper_scissors();
+        let rounds = play(&game, &parse(EXAMPLE).unwrap(), Reading::Outcome).unwrap();
+        let lines = breakdown(&game, &rounds);
+        let lines = lines.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("round  theirs    code  mine"));
+        assert_eq!(
+            lines[3].split_whitespace().collect::<Vec<_>>(),
+            ["3", "Scissors", "Z", "Rock", "Win", "1", "6", "7", "12"]
+        );
+    }
+
+    #[test]
+    fn scores_bigger_games() {
+        let game =
+            Game::load(Path::new(env!("CARGO_MANIFEST_DIR")).join("games/rpsls.txt")).unwrap();
+        let guide = "A Y\nB X\nC Z\nD V\nE W\n";
+
+        // Lizard loses to Rock, Paper beats Spock, Scissors beat Paper, Rock
+        // beats Lizard, Spock beats Scissors
+        assert_eq!(
+            total_score(&game, guide, Reading::Figure).unwrap(),
+            4 + 3 + 6 + 5 + 6 + 1 + 6 + 2 + 6
+        );
+        // Rock draws with Rock, Rock loses to Spock, Lizard is the first move
+        // listed that beats Paper
+        assert_eq!(
+            play(
+                &game,
+                &parse_guide(&game, "A Y\nB X\nC Z\n").unwrap(),
+                Reading::Outcome
+            )
+            .unwrap()
+            .iter()
+            .map(Round::score)
+            .collect::<Vec<_>>(),
+            [1 + 3, 1, 4 + 6]
+        );
+        assert!(total_score(&game, guide, Reading::Outcome).is_err());
+        assert!(parse_guide(&game, "F X\n").is_err());
+    }
+}
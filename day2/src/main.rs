@@ -1,92 +1,93 @@
-use anyhow::{anyhow, Error as AnyhowError};
-use std::{error::Error, fs, str::FromStr};
+use anyhow::{bail, Error as AnyhowError};
+use aoc_common::{DayRun, Flag};
+use day2::{
+    game::Game,
+    optimize::optimize,
+    tournament::{tournament, CopyLast, FrequencyCounter, Random, Sequence, Strategy},
+    Reading,
+};
 
-#[derive(Debug, Clone, Copy)]
-enum Outcome {
-    Win = 6,
-    Lose = 0,
-    Draw = 3,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Figure {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
-}
+/// `[--verbose] [--optimize] [--tournament ROUNDS [--seed S] [--sequence FILE]]
+/// [--game FILE]`
+const FLAGS: [Flag; 6] = [
+    Flag::switch("verbose"),
+    Flag::switch("optimize"),
+    Flag::value("tournament"),
+    Flag::value("seed"),
+    Flag::value("sequence"),
+    Flag::value("game"),
+];
 
-impl FromStr for Figure {
-    type Err = AnyhowError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "A" | "X" => Ok(Figure::Rock),
-            "B" | "Y" => Ok(Figure::Paper),
-            "C" | "Z" => Ok(Figure::Scissors),
-            s => Err(anyhow!("Invalid figure code {}", s)),
+/// Scores the guide under the reading of each part, in the given game or
+/// else in Rock-Paper-Scissors, printing every round if verbose and how every
+/// other cipher of the codes would score if optimizing. A tournament pits the
+/// opponent's figures of the guide, and those of the sequence file if given,
+/// against the other strategies.
+fn run(day: &DayRun) -> Result<(), AnyhowError> {
+    let verbose = day.options.is_set("verbose");
+    let optimizing = day.options.is_set("optimize");
+    let tournament_rounds = day.options.parse::<usize>("tournament")?;
+    if tournament_rounds.is_none() {
+        for flag in ["seed", "sequence"] {
+            if day.options.is_set(flag) {
+                bail!("--{} needs --tournament", flag);
+            }
         }
     }
-}
+    let seed = day.options.parse::<u64>("seed")?.unwrap_or(0);
+    let game = match day.options.value("game") {
+        Some(path) => Game::load(path)?,
+        None => Game::rock_paper_scissors(),
+    };
+    let sequence = day
+        .options
+        .value("sequence")
+        .map(|path| Sequence::load(&game, path))
+        .transpose()?;
+    let input = day.read_input()?;
+    let guide = day2::parse_guide(&game, &input)?;
 
-impl FromStr for Outcome {
-    type Err = AnyhowError;
+    for &part in &day.parts {
+        let reading = if part == 1 {
+            Reading::Figure
+        } else {
+            Reading::Outcome
+        };
+        let rounds = day2::play(&game, &guide, reading)?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "X" => Ok(Outcome::Lose),
-            "Y" => Ok(Outcome::Draw),
-            "Z" => Ok(Outcome::Win),
-            s => Err(anyhow!("Invalid outcome code {}", s)),
+        if verbose {
+            println!("Part {}, {}:", part, reading);
+            print!("{}", day2::breakdown(&game, &rounds));
+        }
+        let total = rounds.iter().map(|r| r.score()).sum::<u32>();
+        println!("Part {}: {}", part, total);
+        if optimizing {
+            println!("\nPart {}, every cipher:", part);
+            print!("{}", optimize(&game, &guide, reading)?.render(&game));
+        }
+        if verbose || optimizing {
+            println!();
         }
     }
-}
-
-fn outcome(my: &Figure, theirs: &Figure) -> Outcome {
-    match (my, theirs) {
-        (Figure::Rock, Figure::Rock) => Outcome::Draw,
-        (Figure::Paper, Figure::Paper) => Outcome::Draw,
-        (Figure::Scissors, Figure::Scissors) => Outcome::Draw,
 
-        (Figure::Scissors, Figure::Paper) => Outcome::Win,
-        (Figure::Rock, Figure::Scissors) => Outcome::Win,
-        (Figure::Paper, Figure::Rock) => Outcome::Win,
+    if let Some(rounds) = tournament_rounds {
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Sequence::from_guide("guide", &game, &input)?),
+            Box::new(Random),
+            Box::new(FrequencyCounter),
+            Box::new(CopyLast),
+        ];
+        if let Some(sequence) = sequence {
+            strategies.push(Box::new(sequence));
+        }
 
-        (Figure::Rock, Figure::Paper) => Outcome::Lose,
-        (Figure::Paper, Figure::Scissors) => Outcome::Lose,
-        (Figure::Scissors, Figure::Rock) => Outcome::Lose,
+        println!("Tournament of {} rounds per match, seed {}:", rounds, seed);
+        print!("{}", tournament(&game, &strategies, rounds, seed).render());
     }
-}
 
-fn inv_outcome(my: &Outcome, theirs: &Figure) -> Figure {
-    match (my, theirs) {
-        (Outcome::Win, Figure::Rock) => Figure::Paper,
-        (Outcome::Win, Figure::Paper) => Figure::Scissors,
-        (Outcome::Win, Figure::Scissors) => Figure::Rock,
-        (Outcome::Lose, Figure::Rock) => Figure::Scissors,
-        (Outcome::Lose, Figure::Paper) => Figure::Rock,
-        (Outcome::Lose, Figure::Scissors) => Figure::Paper,
-        (Outcome::Draw, fig) => *fig,
-    }
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let input = fs::read_to_string("./input.txt")?;
-
-    let total_score = input
-        .lines()
-        .filter_map(|s| {
-            s.split_once(" ")
-                .map(|(l, r)| (l.parse::<Figure>().ok(), r.parse::<Outcome>().ok()))
-        })
-        .filter_map(|p| match p {
-            (None, _) | (_, None) => None,
-            (Some(theirs), Some(outcome)) => {
-                Some(outcome as u16 + inv_outcome(&outcome, &theirs) as u16)
-            }
-        })
-        .sum::<u16>();
-
-    println!("Score: {}", total_score);
-
-    Ok(())
+fn main() -> Result<(), AnyhowError> {
+    aoc_common::day_main_with(2, day2::solve_part1, day2::solve_part2, &FLAGS, run)
 }
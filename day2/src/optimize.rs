@@ -0,0 +1,249 @@
+//! Scores the strategy guide under every cipher of its second column, since
+//! the elf never got to say what X, Y and Z mean.
+
+use crate::{
+    game::{Code, Figure, Game, Outcome},
+    Reading,
+};
+use anyhow::{bail, Error as AnyhowError};
+use itertools::Itertools;
+use std::{cmp::Reverse, collections::HashMap, fmt::Write};
+
+/// What each code of the second column stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cipher {
+    Figures(Vec<(Code, Figure)>),
+    Outcomes(Vec<(Code, Outcome)>),
+}
+
+impl Cipher {
+    /// The cipher the game definition gives the codes under `reading`.
+    pub fn of_game(game: &Game, reading: Reading) -> Self {
+        match reading {
+            Reading::Figure => {
+                Cipher::Figures(game.figures().map(|f| (game.my_code(f), f)).collect())
+            }
+            Reading::Outcome => Cipher::Outcomes(
+                Outcome::ALL
+                    .into_iter()
+                    .map(|o| (game.outcome_code(o), o))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn describe(&self, game: &Game) -> String {
+        match self {
+            Cipher::Figures(pairs) => pairs
+                .iter()
+                .map(|&(code, f)| format!("{}={}", code, game.name(f)))
+                .join(" "),
+            Cipher::Outcomes(pairs) => pairs
+                .iter()
+                .map(|(code, o)| format!("{}={}", code, o))
+                .join(" "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranked {
+    pub cipher: Cipher,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Optimization {
+    pub reading: Reading,
+    /// Every cipher, best first, ties in the order they were enumerated in.
+    pub ranked: Vec<Ranked>,
+    /// Mean score over all ciphers, that is the expected score under one
+    /// drawn uniformly at random.
+    pub expected: f64,
+}
+
+impl Optimization {
+    pub fn best(&self) -> &Ranked {
+        &self.ranked[0]
+    }
+
+    pub fn worst(&self) -> &Ranked {
+        &self.ranked[self.ranked.len() - 1]
+    }
+
+    /// The ranked ciphers, the game's own marked with a `*`, then the best,
+    /// worst and expected scores.
+    pub fn render(&self, game: &Game) -> String {
+        let own = Cipher::of_game(game, self.reading);
+        let mut out = format!("{:>4}  {:>6}  cipher\n", "rank", "score");
+
+        for (i, r) in self.ranked.iter().enumerate() {
+            let mark = if r.cipher == own { " *" } else { "" };
+            writeln!(
+                out,
+                "{:>4}  {:>6}  {}{}",
+                i + 1,
+                r.score,
+                r.cipher.describe(game),
+                mark
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "best {}, worst {}, expected {:.2}",
+            self.best().score,
+            self.worst().score,
+            self.expected
+        )
+        .unwrap();
+
+        out
+    }
+}
+
+/// Scores the rounds, counted by opponent figure and code index, under every
+/// assignment of `targets` to `codes`.
+fn rank<T: Copy>(
+    codes: &[Code],
+    targets: &[T],
+    rounds: &HashMap<(Figure, usize), u32>,
+    score: impl Fn(Figure, T) -> u32,
+    cipher: impl Fn(Vec<(Code, T)>) -> Cipher,
+) -> Vec<Ranked> {
+    targets
+        .iter()
+        .copied()
+        .permutations(targets.len())
+        .map(|assigned| Ranked {
+            score: rounds
+                .iter()
+                .map(|(&(theirs, code), &n)| n * score(theirs, assigned[code]))
+                .sum(),
+            cipher: cipher(codes.iter().copied().zip(assigned).collect()),
+        })
+        .collect()
+}
+
+/// Ranks every cipher of the second column under `reading`: every way of
+/// assigning the game's figure codes to its figures, or the outcome codes to
+/// the outcomes.
+pub fn optimize(
+    game: &Game,
+    guide: &[(Figure, Code)],
+    reading: Reading,
+) -> Result<Optimization, AnyhowError> {
+    let codes = match reading {
+        Reading::Figure => game.figures().map(|f| game.my_code(f)).collect_vec(),
+        Reading::Outcome => Outcome::ALL.map(|o| game.outcome_code(o)).to_vec(),
+    };
+
+    let mut rounds = HashMap::new();
+    for &(theirs, code) in guide {
+        let Some(i) = codes.iter().position(|&c| c == code) else {
+            bail!("Code {} is not one of {}", code, codes.iter().join(", "));
+        };
+        *rounds.entry((theirs, i)).or_insert(0) += 1;
+    }
+
+    let mut ranked = match reading {
+        Reading::Figure => rank(
+            &codes,
+            &game.figures().collect_vec(),
+            &rounds,
+            |theirs, my| game.figure_points(my) + game.outcome_points(game.outcome(my, theirs)),
+            Cipher::Figures,
+        ),
+        Reading::Outcome => rank(
+            &codes,
+            &Outcome::ALL,
+            &rounds,
+            |theirs, outcome| {
+                game.figure_points(game.inv_outcome(outcome, theirs)) + game.outcome_points(outcome)
+            },
+            Cipher::Outcomes,
+        ),
+    };
+    ranked.sort_by_key(|r| Reverse(r.score));
+
+    let expected = ranked.iter().map(|r| r.score as f64).sum::<f64>() / ranked.len() as f64;
+
+    Ok(Optimization {
+        reading,
+        ranked,
+        expected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, total_score};
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn ranks_every_cipher() {
+        let game = Game::rock_paper_scissors();
+        let guide = parse(EXAMPLE).unwrap();
+
+        for reading in [Reading::Figure, Reading::Outcome] {
+            let optimization = optimize(&game, &guide, reading).unwrap();
+            assert_eq!(optimization.ranked.len(), 6);
+            assert!(optimization
+                .ranked
+                .windows(2)
+                .all(|w| w[0].score >= w[1].score));
+
+            // The game's own cipher scores what the parts do
+            let own = Cipher::of_game(&game, reading);
+            let own = optimization.ranked.iter().find(|r| r.cipher == own);
+            assert_eq!(
+                own.unwrap().score,
+                total_score(&game, EXAMPLE, reading).unwrap()
+            );
+        }
+
+        // Rock Y, Paper X, Scissors Z: playing Y=Paper X=Scissors Z=Rock wins
+        // all three rounds
+        let figures = optimize(&game, &guide, Reading::Figure).unwrap();
+        assert_eq!(figures.best().score, 2 + 3 + 1 + 3 * 6);
+        assert_eq!(figures.worst().score, 3 + 1 + 2);
+        let mean = figures.ranked.iter().map(|r| r.score).sum::<u32>() as f64 / 6.0;
+        assert_eq!(figures.expected, mean);
+    }
+
+    #[test]
+    fn renders_the_ranking() {
+        let game = Game::rock_paper_scissors();
+        let guide = parse(EXAMPLE).unwrap();
+        let rendered = optimize(&game, &guide, Reading::Outcome)
+            .unwrap()
+            .render(&game);
+        let lines = rendered.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "rank   score  cipher");
+        assert!(lines[1..7]
+            .iter()
+            .any(|l| l.ends_with("X=Lose Y=Draw Z=Win *")));
+        assert!(lines[7].starts_with("best "));
+    }
+
+    #[test]
+    fn needs_codes_of_the_reading() {
+        let game =
+            Game::load(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("games/rpsls.txt"))
+                .unwrap();
+        let guide = crate::parse_guide(&game, "A V\nB Z\n").unwrap();
+
+        assert_eq!(
+            optimize(&game, &guide, Reading::Figure)
+                .unwrap()
+                .ranked
+                .len(),
+            120
+        );
+        assert!(optimize(&game, &guide, Reading::Outcome).is_err());
+    }
+}
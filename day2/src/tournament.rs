@@ -0,0 +1,413 @@
+//! Round-robin tournaments between strategies, every pair of them playing a
+//! match of a fixed number of rounds.
+
+use crate::{
+    game::{Figure, Game, Outcome},
+    parse_guide,
+};
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{
+    parse::{self, parse_lines},
+    LineError,
+};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{
+    cmp::{Ordering, Reverse},
+    fmt::Write,
+    fs,
+    path::Path,
+};
+
+/// A way of picking figures. Strategies only look at the rounds played so
+/// far, as `(mine, theirs)`, so every match starts from scratch.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    fn pick(&self, game: &Game, history: &[(Figure, Figure)], rng: &mut dyn RngCore) -> Figure;
+}
+
+fn random_figure(game: &Game, rng: &mut dyn RngCore) -> Figure {
+    Figure(rng.gen_range(0..game.figures().count()))
+}
+
+/// Plays the given figures in turn, starting over when they run out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    name: String,
+    figures: Vec<Figure>,
+}
+
+impl Sequence {
+    pub fn new(name: impl Into<String>, figures: Vec<Figure>) -> Result<Self, AnyhowError> {
+        let name = name.into();
+        if figures.is_empty() {
+            bail!("Sequence {} has no figures", name);
+        }
+        Ok(Sequence { name, figures })
+    }
+
+    /// The opponent's figures of a strategy guide.
+    pub fn from_guide(
+        name: impl Into<String>,
+        game: &Game,
+        input: &str,
+    ) -> Result<Self, AnyhowError> {
+        let figures = parse_guide(game, input)?
+            .into_iter()
+            .map(|(theirs, _)| theirs)
+            .collect();
+        Sequence::new(name, figures)
+    }
+
+    /// Figures named as in `game`, separated by whitespace over any number
+    /// of lines; `#` starts a comment.
+    pub fn parse(name: impl Into<String>, game: &Game, text: &str) -> Result<Self, AnyhowError> {
+        let lines = parse_lines(text, |line| {
+            let figures = line.split('#').next().unwrap_or_default();
+            figures
+                .split_whitespace()
+                .map(|f| {
+                    game.figure(f)
+                        .ok_or_else(|| LineError::at(line, f, "a figure of the game"))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+
+        Sequence::new(name, lines.into_iter().flatten().collect())
+    }
+
+    /// Reads the figures from the file at `path`, see [`Sequence::parse`].
+    /// The sequence is named after the file.
+    pub fn load(game: &Game, path: impl AsRef<Path>) -> Result<Self, AnyhowError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Can't read {}: {}", path.display(), e))?;
+        let name = path
+            .file_stem()
+            .map_or("sequence".into(), |s| s.to_string_lossy());
+
+        Sequence::parse(name, game, &text)
+            .map_err(|e| parse::attach_file(e, &path.display().to_string()))
+    }
+}
+
+impl Strategy for Sequence {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn pick(&self, _: &Game, history: &[(Figure, Figure)], _: &mut dyn RngCore) -> Figure {
+        self.figures[history.len() % self.figures.len()]
+    }
+}
+
+/// Any figure, uniformly at random.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Random;
+
+impl Strategy for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn pick(&self, game: &Game, _: &[(Figure, Figure)], rng: &mut dyn RngCore) -> Figure {
+        random_figure(game, rng)
+    }
+}
+
+/// Beats the figure the opponent played most so far, the first one listed
+/// on ties. Random in the first round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> &str {
+        "frequency"
+    }
+
+    fn pick(&self, game: &Game, history: &[(Figure, Figure)], rng: &mut dyn RngCore) -> Figure {
+        if history.is_empty() {
+            return random_figure(game, rng);
+        }
+
+        let mut counts = vec![0; game.figures().count()];
+        for &(_, theirs) in history {
+            counts[theirs.0] += 1;
+        }
+        let most = game
+            .figures()
+            .max_by_key(|&f| (counts[f.0], Reverse(f.0)))
+            .expect("games have figures");
+
+        game.inv_outcome(Outcome::Win, most)
+    }
+}
+
+/// Plays whatever the opponent played last. Random in the first round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyLast;
+
+impl Strategy for CopyLast {
+    fn name(&self) -> &str {
+        "copy last"
+    }
+
+    fn pick(&self, game: &Game, history: &[(Figure, Figure)], rng: &mut dyn RngCore) -> Figure {
+        match history.last() {
+            Some(&(_, theirs)) => theirs,
+            None => random_figure(game, rng),
+        }
+    }
+}
+
+/// Points each player of a match scored, in the order they were given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub players: (usize, usize),
+    pub points: (u32, u32),
+}
+
+/// Plays `rounds` rounds of `game` between `a` and `b`.
+pub fn play_match(
+    game: &Game,
+    a: &dyn Strategy,
+    b: &dyn Strategy,
+    rounds: usize,
+    rng: &mut dyn RngCore,
+) -> (u32, u32) {
+    let mut history_a = Vec::with_capacity(rounds);
+    let mut history_b = Vec::with_capacity(rounds);
+    let mut points = (0, 0);
+    let score = |my, theirs| game.figure_points(my) + game.outcome_points(game.outcome(my, theirs));
+
+    for _ in 0..rounds {
+        let fig_a = a.pick(game, &history_a, rng);
+        let fig_b = b.pick(game, &history_b, rng);
+
+        points.0 += score(fig_a, fig_b);
+        points.1 += score(fig_b, fig_a);
+        history_a.push((fig_a, fig_b));
+        history_b.push((fig_b, fig_a));
+    }
+
+    points
+}
+
+/// How a strategy fared over the whole tournament. Winning a match is worth
+/// 3 table points and a draw 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub won: u32,
+    pub drawn: u32,
+    pub lost: u32,
+    /// Points scored over every round played.
+    pub points: u32,
+}
+
+impl Standing {
+    pub fn table_points(&self) -> u32 {
+        3 * self.won + self.drawn
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    pub matches: Vec<Match>,
+    /// Best first, by table points then points scored, ties in the order the
+    /// strategies were given.
+    pub leaderboard: Vec<Standing>,
+}
+
+/// Plays a match of `rounds` rounds between every two strategies, the random
+/// choices all drawn from a generator seeded with `seed`.
+pub fn tournament(
+    game: &Game,
+    strategies: &[Box<dyn Strategy>],
+    rounds: usize,
+    seed: u64,
+) -> Tournament {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut standings = strategies
+        .iter()
+        .map(|s| Standing {
+            name: s.name().to_owned(),
+            won: 0,
+            drawn: 0,
+            lost: 0,
+            points: 0,
+        })
+        .collect::<Vec<_>>();
+    let mut matches = vec![];
+
+    for a in 0..strategies.len() {
+        for b in a + 1..strategies.len() {
+            let points = play_match(game, &*strategies[a], &*strategies[b], rounds, &mut rng);
+
+            standings[a].points += points.0;
+            standings[b].points += points.1;
+            match points.0.cmp(&points.1) {
+                Ordering::Greater => {
+                    standings[a].won += 1;
+                    standings[b].lost += 1;
+                }
+                Ordering::Less => {
+                    standings[a].lost += 1;
+                    standings[b].won += 1;
+                }
+                Ordering::Equal => {
+                    standings[a].drawn += 1;
+                    standings[b].drawn += 1;
+                }
+            }
+            matches.push(Match {
+                players: (a, b),
+                points,
+            });
+        }
+    }
+
+    let mut leaderboard = standings;
+    leaderboard.sort_by_key(|s| Reverse((s.table_points(), s.points)));
+
+    Tournament {
+        matches,
+        leaderboard,
+    }
+}
+
+impl Tournament {
+    pub fn render(&self) -> String {
+        let width = self
+            .leaderboard
+            .iter()
+            .map(|s| s.name.len())
+            .max()
+            .unwrap_or(0);
+        let mut out = format!(
+            "{:>4}  {:<w$}  {:>3}  {:>5}  {:>4}  {:>5}  {:>7}\n",
+            "rank",
+            "strategy",
+            "won",
+            "drawn",
+            "lost",
+            "table",
+            "points",
+            w = width.max(8)
+        );
+
+        for (i, s) in self.leaderboard.iter().enumerate() {
+            writeln!(
+                out,
+                "{:>4}  {:<w$}  {:>3}  {:>5}  {:>4}  {:>5}  {:>7}",
+                i + 1,
+                s.name,
+                s.won,
+                s.drawn,
+                s.lost,
+                s.table_points(),
+                s.points,
+                w = width.max(8)
+            )
+            .unwrap();
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::ParseReport;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    fn strategies(game: &Game) -> Vec<Box<dyn Strategy>> {
+        vec![
+            Box::new(Sequence::from_guide("guide", game, EXAMPLE).unwrap()),
+            Box::new(Random),
+            Box::new(FrequencyCounter),
+            Box::new(CopyLast),
+        ]
+    }
+
+    #[test]
+    fn strategies_pick_from_history() {
+        let game = Game::rock_paper_scissors();
+        let [rock, paper, scissors] =
+            ["Rock", "Paper", "Scissors"].map(|name| game.figure(name).unwrap());
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let history = [(rock, paper), (rock, scissors), (paper, paper)];
+
+        let guide = Sequence::from_guide("guide", &game, EXAMPLE).unwrap();
+        assert_eq!(guide.pick(&game, &history, &mut rng), rock);
+        assert_eq!(guide.pick(&game, &history[..2], &mut rng), scissors);
+        assert_eq!(FrequencyCounter.pick(&game, &history, &mut rng), scissors);
+        assert_eq!(CopyLast.pick(&game, &history, &mut rng), paper);
+        assert!(Sequence::new("empty", vec![]).is_err());
+    }
+
+    #[test]
+    fn counters_beat_a_fixed_sequence() {
+        let game = Game::rock_paper_scissors();
+        let rock = Sequence::new("rock", vec![game.figure("Rock").unwrap()]).unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        let (ours, theirs) = play_match(&game, &FrequencyCounter, &rock, 100, &mut rng);
+        // Whatever the random first round, paper beats rock from then on
+        assert!(ours >= 99 * (2 + 6));
+        assert!(theirs <= 1 + 6 + 99);
+    }
+
+    #[test]
+    fn parses_sequences_of_figure_names() {
+        let game = Game::rock_paper_scissors();
+        let [rock, paper] = ["Rock", "Paper"].map(|name| game.figure(name).unwrap());
+
+        let sequence = Sequence::parse("rrp", &game, "# Rock twice\nRock Rock\n\n  Paper\n");
+        assert_eq!(
+            sequence.unwrap(),
+            Sequence::new("rrp", vec![rock, rock, paper]).unwrap()
+        );
+
+        let report = Sequence::parse("bad", &game, "Rock Lizard\n")
+            .unwrap_err()
+            .downcast::<ParseReport>()
+            .unwrap();
+        assert_eq!((report.errors[0].line, report.errors[0].column), (1, 6));
+        assert_eq!(report.errors[0].expected, "a figure of the game");
+
+        assert!(Sequence::parse("empty", &game, "# Nothing\n").is_err());
+    }
+
+    #[test]
+    fn seeded_tournaments_repeat() {
+        let game = Game::rock_paper_scissors();
+        let result = tournament(&game, &strategies(&game), 300, 7);
+
+        assert_eq!(result, tournament(&game, &strategies(&game), 300, 7));
+        assert_eq!(result.matches.len(), 6);
+        assert_eq!(result.leaderboard.len(), 4);
+        for s in &result.leaderboard {
+            assert_eq!(s.won + s.drawn + s.lost, 3);
+        }
+        assert!(result
+            .leaderboard
+            .windows(2)
+            .all(|w| w[0].table_points() >= w[1].table_points()));
+
+        let points = result.leaderboard.iter().map(|s| s.points).sum::<u32>();
+        let matches = result
+            .matches
+            .iter()
+            .map(|m| m.points.0 + m.points.1)
+            .sum::<u32>();
+        assert_eq!(points, matches);
+
+        let rendered = result.render();
+        assert_eq!(rendered.lines().count(), 5);
+        assert!(rendered.starts_with("rank  strategy   won"));
+    }
+}
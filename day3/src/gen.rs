@@ -0,0 +1,71 @@
+//! Random rucksack lists with known answers.
+
+use proptest::{collection::vec, prelude::*, sample::select};
+
+/// A group of three rucksacks, along with the item each of them has in both
+/// compartments and the badge they all carry.
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub rucksacks: [String; 3],
+    pub shared: [char; 3],
+    pub badge: char,
+}
+
+/// Compartment holding `first` and `len` items in total, the others drawn
+/// from `pool`.
+fn compartment(first: Vec<char>, pool: Vec<char>, len: usize) -> impl Strategy<Value = String> {
+    vec(select(pool), len - first.len())
+        .prop_map(move |rest| first.iter().chain(&rest).copied().collect::<Vec<_>>())
+        .prop_shuffle()
+        .prop_map(|items| items.into_iter().collect())
+}
+
+/// Rucksack whose compartments only have `shared` in common. The badge goes
+/// in the first one.
+fn rucksack(
+    shared: char,
+    badge: char,
+    left: Vec<char>,
+    right: Vec<char>,
+) -> impl Strategy<Value = String> {
+    (2..16usize)
+        .prop_flat_map(move |len| {
+            (
+                compartment(vec![shared, badge], left.clone(), len),
+                compartment(vec![shared], right.clone(), len),
+            )
+        })
+        .prop_map(|(left, right)| left + &right)
+}
+
+/// A group drawn from disjoint sets of letters, so that the shared items and
+/// the badge are the only items in common.
+pub fn group() -> impl Strategy<Value = Group> {
+    let letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+
+    Just(letters).prop_shuffle().prop_flat_map(|l| {
+        let rucksack = |i: usize| {
+            let pool = &l[4 + 16 * i..];
+            rucksack(l[1 + i], l[0], pool[..8].to_vec(), pool[8..16].to_vec())
+        };
+        let (badge, shared) = (l[0], [l[1], l[2], l[3]]);
+
+        (rucksack(0), rucksack(1), rucksack(2)).prop_map(move |(a, b, c)| Group {
+            rucksacks: [a, b, c],
+            shared,
+            badge,
+        })
+    })
+}
+
+pub fn to_input(groups: &[Group]) -> String {
+    groups
+        .iter()
+        .flat_map(|g| &g.rucksacks)
+        .map(|r| format!("{}\n", r))
+        .collect()
+}
+
+pub fn input() -> impl Strategy<Value = String> {
+    vec(group(), 1..20).prop_map(|groups| to_input(&groups))
+}
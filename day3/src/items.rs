@@ -0,0 +1,177 @@
+//! Item types and sets of them. With 52 item types, a set fits in a `u64`
+//! whose bit `p` stands for the item type of priority `p`.
+
+use aoc_common::LineError;
+use std::{
+    fmt::{self, Display},
+    iter,
+    str::FromStr,
+};
+
+/// An item type, as its priority: 1 to 26 for `a` to `z`, 27 to 52 for `A`
+/// to `Z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Item(u8);
+
+impl Item {
+    /// The item type a letter stands for, `None` for anything but an ASCII
+    /// letter.
+    pub fn new(c: char) -> Option<Self> {
+        match c {
+            'a'..='z' => Some(Item(c as u8 - b'a' + 1)),
+            'A'..='Z' => Some(Item(c as u8 - b'A' + 27)),
+            _ => None,
+        }
+    }
+
+    pub fn priority(self) -> u32 {
+        self.0 as u32
+    }
+}
+
+impl From<Item> for char {
+    fn from(item: Item) -> Self {
+        match item.0 {
+            p @ 1..=26 => (b'a' + p - 1) as char,
+            p => (b'A' + p - 27) as char,
+        }
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+
+    /// Every one of the 52 item types.
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    pub fn insert(&mut self, item: Item) {
+        self.0 |= 1 << item.0;
+    }
+
+    pub fn contains(self, item: Item) -> bool {
+        self.0 & (1 << item.0) != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    /// The item types of the set, by increasing priority.
+    pub fn iter(self) -> impl Iterator<Item = Item> {
+        let mut bits = self.0;
+        iter::from_fn(move || {
+            let p = bits.trailing_zeros();
+            bits &= bits.wrapping_sub(1);
+            (p < 64).then_some(Item(p as u8))
+        })
+    }
+}
+
+impl FromIterator<Item> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = Item>>(iter: T) -> Self {
+        let mut set = ItemSet::EMPTY;
+        for item in iter {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+impl FromStr for ItemSet {
+    type Err = LineError;
+
+    /// The item types of a line of item letters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.char_indices()
+            .map(|(i, c)| Item::new(c).ok_or_else(|| LineError::at(s, &s[i..], "an item letter")))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(s: &str) -> Vec<char> {
+        s.parse::<ItemSet>()
+            .unwrap()
+            .iter()
+            .map(char::from)
+            .collect()
+    }
+
+    #[test]
+    fn maps_letters_to_priorities() {
+        let priority = |c| Item::new(c).map(Item::priority);
+
+        assert_eq!(priority('a'), Some(1));
+        assert_eq!(priority('z'), Some(26));
+        assert_eq!(priority('A'), Some(27));
+        assert_eq!(priority('Z'), Some(52));
+        for c in ['`', '{', '@', '[', '0', ' ', 'é'] {
+            assert_eq!(priority(c), None);
+        }
+
+        for c in ('a'..='z').chain('A'..='Z') {
+            assert_eq!(char::from(Item::new(c).unwrap()), c);
+        }
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = "vJrwpWtwJgWr".parse::<ItemSet>().unwrap();
+        let b = "hcsFMMfFFhFp".parse::<ItemSet>().unwrap();
+
+        assert_eq!((a.len(), b.len()), (8, 7));
+        assert!(a.contains(Item::new('p').unwrap()));
+        assert!(!a.contains(Item::new('P').unwrap()));
+        assert_eq!(
+            a.intersection(b).iter().map(char::from).collect::<Vec<_>>(),
+            ['p']
+        );
+        assert_eq!(a.union(b).len(), 8 + 7 - 1);
+        assert!(a.intersection(ItemSet::EMPTY).is_empty());
+        assert_eq!(a.intersection(ItemSet::ALL), a);
+        assert_eq!(ItemSet::ALL.len(), 52);
+    }
+
+    #[test]
+    fn iterates_by_priority() {
+        assert_eq!(items("ZzAa"), ['a', 'z', 'A', 'Z']);
+        assert!(items("").is_empty());
+        assert_eq!(ItemSet::ALL.iter().last(), Item::new('Z'));
+    }
+
+    #[test]
+    fn rejects_other_characters() {
+        assert_eq!(
+            "abc1".parse::<ItemSet>(),
+            Err(LineError::new(4, "an item letter"))
+        );
+        assert_eq!(
+            "aé[".parse::<ItemSet>(),
+            Err(LineError::new(2, "an item letter"))
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use anyhow::{bail, Error as AnyhowError};
+use aoc_common::{parse::parse_lines, Answer, LineError, ParseReport};
+use items::{Item, ItemSet};
+use itertools::Itertools;
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+pub mod items;
+
+/// Parses the rucksack list, one line of item letters per rucksack.
+pub fn parse(input: &str) -> Result<Vec<&str>, AnyhowError> {
+    Ok(parse_lines(input, |s| s.parse::<ItemSet>().map(|_| s))?)
+}
+
+/// The item type found in both compartments of a rucksack, which must be
+/// the only one.
+fn shared_item(s: &str) -> Result<Item, LineError> {
+    s.parse::<ItemSet>()?;
+    if s.len() % 2 == 1 {
+        return Err(LineError::new(
+            1,
+            format!("two compartments of the same size, not {} items", s.len()),
+        ));
+    }
+
+    let (left, right) = s.split_at(s.len() / 2);
+    let shared = left.parse::<ItemSet>()?.intersection(right.parse()?);
+
+    match shared.iter().collect::<Vec<_>>()[..] {
+        [item] => Ok(item),
+        [] => Err(LineError::new(
+            1,
+            "compartments sharing an item type, these share none",
+        )),
+        _ => Err(LineError::new(
+            1,
+            format!(
+                "compartments sharing a single item type, these share {}",
+                shared.iter().join(", ")
+            ),
+        )),
+    }
+}
+
+/// The item type each rucksack has in both compartments.
+pub fn shared_items(input: &str) -> Result<Vec<Item>, AnyhowError> {
+    Ok(parse_lines(input, shared_item)?)
+}
+
+/// The badge of each group of `size` rucksacks, in order: the only item type
+/// all of them carry. Malformed rucksacks fail even outside strict mode, as
+/// skipping one would shift every later group.
+pub fn badges(input: &str, size: usize) -> Result<Vec<Item>, AnyhowError> {
+    let mut rucksacks = vec![];
+    let mut errors = vec![];
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.parse::<ItemSet>() {
+            Ok(rucksack) => rucksacks.push(rucksack),
+            Err(e) => errors.extend(ParseReport::single(i, line, e).errors),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(ParseReport { errors }.into());
+    }
+
+    if size == 0 || rucksacks.len() % size != 0 {
+        bail!(
+            "{} rucksacks can't be split into groups of {}",
+            rucksacks.len(),
+            size
+        );
+    }
+
+    rucksacks
+        .chunks(size)
+        .enumerate()
+        .map(|(i, group)| {
+            let common = group
+                .iter()
+                .fold(ItemSet::ALL, |acc, &it| acc.intersection(it));
+
+            match common.iter().collect::<Vec<_>>()[..] {
+                [badge] => Ok(badge),
+                [] => bail!("Group {} has no item type in common", i + 1),
+                _ => bail!(
+                    "Group {} has several item types in common: {}",
+                    i + 1,
+                    common.iter().join(", ")
+                ),
+            }
+        })
+        .collect()
+}
+
+fn priorities(items: Vec<Item>) -> u32 {
+    items.into_iter().map(Item::priority).sum()
+}
+
+/// Sum of the priorities of the shared items and of the badges of groups of
+/// `size` rucksacks.
+pub fn solve(input: &str, size: usize) -> Result<(u32, u32), AnyhowError> {
+    Ok((
+        priorities(shared_items(input)?),
+        priorities(badges(input, size)?),
+    ))
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(priorities(shared_items(input)?).into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(priorities(badges(input, 3)?).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use proptest::{collection::vec, prelude::*};
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(157));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(70));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    fn letters(items: Vec<Item>) -> Vec<char> {
+        items.into_iter().map(char::from).collect()
+    }
+
+    fn priority(c: char) -> u32 {
+        Item::new(c).unwrap().priority()
+    }
+
+    fn errors(input: &str) -> Vec<(usize, String)> {
+        let report = shared_items(input)
+            .unwrap_err()
+            .downcast::<ParseReport>()
+            .unwrap();
+        report
+            .errors
+            .into_iter()
+            .map(|e| (e.line, e.expected))
+            .collect()
+    }
+
+    #[test]
+    fn finds_the_shared_items() {
+        assert_eq!(
+            letters(shared_items(EXAMPLE).unwrap()),
+            ['p', 'L', 'P', 'v', 't', 's']
+        );
+    }
+
+    #[test]
+    fn rejects_rucksacks_not_sharing_one_item() {
+        assert_eq!(
+            errors("abcb\nabcab\nabcdef\nabAbAc\n"),
+            [
+                (
+                    2,
+                    "two compartments of the same size, not 5 items".to_owned()
+                ),
+                (
+                    3,
+                    "compartments sharing an item type, these share none".to_owned()
+                ),
+                (
+                    4,
+                    "compartments sharing a single item type, these share b, A".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        assert_eq!(letters(badges(EXAMPLE, 3).unwrap()), ['r', 'Z']);
+        assert_eq!(
+            letters(badges("abc\ncd\nxy\nyzY\n", 2).unwrap()),
+            ['c', 'y']
+        );
+        assert_eq!(letters(badges("aa\nB\n", 1).unwrap()), ['a', 'B']);
+        assert_eq!(
+            badges(EXAMPLE, 6).unwrap_err().to_string(),
+            "Group 1 has no item type in common"
+        );
+        assert!(badges(EXAMPLE, 4).is_err());
+        assert!(badges(EXAMPLE, 0).is_err());
+        assert_eq!(
+            badges("ab\nba\n", 2).unwrap_err().to_string(),
+            "Group 1 has several item types in common: a, b"
+        );
+
+        assert_eq!(solve(EXAMPLE, 3).unwrap(), (157, 70));
+    }
+
+    proptest! {
+        #[test]
+        fn solves_generated_groups(groups in vec(gen::group(), 1..20)) {
+            let input = gen::to_input(&groups);
+
+            let shared = groups.iter().flat_map(|g| g.shared).map(priority).sum::<u32>();
+            let badges = groups.iter().map(|g| priority(g.badge)).sum::<u32>();
+
+            prop_assert_eq!(solve_part1(&input).unwrap(), shared.into());
+            prop_assert_eq!(solve_part2(&input).unwrap(), badges.into());
+        }
+    }
+}
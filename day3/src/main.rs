@@ -1,29 +1,33 @@
-use itertools::Itertools;
-use std::{collections::HashSet, error::Error, fs};
+use anyhow::{bail, Error as AnyhowError};
+use aoc_common::{DayRun, Flag};
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let input = fs::read_to_string("./input.txt")?;
+/// `[--group-size N]`
+const FLAGS: [Flag; 1] = [Flag::value("group-size")];
 
-    let chunks = input
-        .lines()
-        .map(|s| s.chars().collect::<HashSet<_>>())
-        .chunks(3);
+/// Solves the requested parts with badges shared by groups of `--group-size`
+/// rucksacks, reporting the size on stderr so the answers keep their usual
+/// layout.
+fn run(day: &DayRun) -> Result<(), AnyhowError> {
+    let size = match day.options.parse::<usize>("group-size")? {
+        Some(0) => bail!("Bad --group-size value 0"),
+        size => size.unwrap_or(3),
+    };
+    let input = day.read_input()?;
 
-    let result: u16 = chunks
-        .into_iter()
-        .filter_map(|chunk| chunk.reduce(|acc, it| acc.intersection(&it).map(|c| *c).collect()))
-        .filter_map(|isec| isec.iter().next().and_then(|c| Some(*c)))
-        .map(|c| {
-            let ascii_code = c as u8;
-            let the_code = if ascii_code > 96 {
-                ascii_code - 96
-            } else {
-                ascii_code - 64 + 26
-            };
-            the_code as u16
-        }).sum();
-
-    println!("Sum: {}", result);
+    for &part in &day.parts {
+        if part == 1 {
+            println!("Part 1: {}", day3::solve_part1(&input)?);
+        } else {
+            let badges = day3::badges(&input, size)?;
+            let priorities = badges.into_iter().map(|b| b.priority()).sum::<u32>();
+            eprintln!("Badges of groups of {} rucksacks", size);
+            println!("Part 2: {}", priorities);
+        }
+    }
 
     Ok(())
 }
+
+fn main() -> Result<(), AnyhowError> {
+    aoc_common::day_main_with(3, day3::solve_part1, day3::solve_part2, &FLAGS, run)
+}
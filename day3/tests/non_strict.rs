@@ -0,0 +1,23 @@
+//! Runs in its own process, as strictness is a global setting the unit tests
+//! rely on being on.
+
+use aoc_common::{parse, ParseReport};
+
+#[test]
+fn badges_never_skip_rucksacks() {
+    parse::set_strict(false);
+
+    // Other parts still skip bad lines
+    assert_eq!(day3::shared_items("aa\nb1\nbb\n").unwrap().len(), 2);
+
+    // Skipping line 2 would pair ab with bc and cd with de, which both share
+    // an item
+    let input = "ab\nb1\nbc\ncd\nde\n";
+
+    let report = day3::badges(input, 2)
+        .unwrap_err()
+        .downcast::<ParseReport>()
+        .unwrap();
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].line, 2);
+}
@@ -0,0 +1,24 @@
+//! Random section assignments.
+
+use crate::Pair;
+use proptest::{collection::vec, prelude::*};
+use std::ops::RangeInclusive;
+
+pub fn range() -> impl Strategy<Value = RangeInclusive<u32>> {
+    (1..100u32, 0..30u32).prop_map(|(start, len)| start..=start + len)
+}
+
+pub fn pair() -> impl Strategy<Value = Pair> {
+    (range(), range())
+}
+
+pub fn to_input(pairs: &[Pair]) -> String {
+    pairs
+        .iter()
+        .map(|(a, b)| format!("{}-{},{}-{}\n", a.start(), a.end(), b.start(), b.end()))
+        .collect()
+}
+
+pub fn input() -> impl Strategy<Value = String> {
+    vec(pair(), 1..50).prop_map(|pairs| to_input(&pairs))
+}
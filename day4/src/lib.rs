@@ -0,0 +1,132 @@
+use anyhow::Error as AnyhowError;
+use aoc_common::{parse::parse_lines, Answer, LineError};
+use std::ops::RangeInclusive;
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+
+pub type Pair = (RangeInclusive<u32>, RangeInclusive<u32>);
+
+/// Parses a `<start>-<end>` range; `line` is the whole line `s` came from.
+fn parse_range(line: &str, s: &str) -> Result<RangeInclusive<u32>, LineError> {
+    let (s1, s2) = s
+        .split_once('-')
+        .ok_or(LineError::at(line, s, "a range like 2-4"))?;
+
+    Ok(RangeInclusive::new(
+        s1.parse::<u32>()
+            .map_err(|_| LineError::at(line, s1, "a section number"))?,
+        s2.parse::<u32>()
+            .map_err(|_| LineError::at(line, s2, "a section number"))?,
+    ))
+}
+
+/// Parses the section assignments of each pair of elves.
+pub fn parse(input: &str) -> Result<Vec<Pair>, AnyhowError> {
+    Ok(parse_lines(input, |s| {
+        let (left, right) = s
+            .split_once(',')
+            .ok_or(LineError::new(1, "two ranges separated by a comma"))?;
+
+        Ok((parse_range(s, left)?, parse_range(s, right)?))
+    })?)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    let result = parse(input)?
+        .into_iter()
+        .map(|(r1, r2)| {
+            let fwd = r1.contains(r2.start()) && r1.contains(r2.end());
+            let rev = r2.contains(r1.start()) && r2.contains(r1.end());
+            if fwd || rev {
+                1
+            } else {
+                0
+            }
+        })
+        .sum::<u32>();
+
+    Ok(result.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    let result = parse(input)?
+        .into_iter()
+        .map(|(r1, r2)| {
+            let fwd = r1.contains(r2.start()) || r1.contains(r2.end());
+            let rev = r2.contains(r1.start()) || r2.contains(r1.end());
+            if fwd || rev {
+                1
+            } else {
+                0
+            }
+        })
+        .sum::<u32>();
+
+    Ok(result.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use proptest::{collection::vec, prelude::*};
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(2));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(4));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    proptest! {
+        #[test]
+        fn parses_generated_pairs(pairs in vec(gen::pair(), 1..50)) {
+            prop_assert_eq!(parse(&gen::to_input(&pairs)).unwrap(), pairs);
+        }
+
+        #[test]
+        fn overlap_is_symmetric(pair in gen::pair()) {
+            let swapped = (pair.1.clone(), pair.0.clone());
+            let (input, swapped) = (gen::to_input(&[pair]), gen::to_input(&[swapped]));
+
+            prop_assert_eq!(solve_part1(&input).unwrap(), solve_part1(&swapped).unwrap());
+            prop_assert_eq!(solve_part2(&input).unwrap(), solve_part2(&swapped).unwrap());
+        }
+
+        #[test]
+        fn counts_containment_and_overlap(pairs in vec(gen::pair(), 1..50)) {
+            let input = gen::to_input(&pairs);
+
+            let contains = |a: &RangeInclusive<u32>, b: &RangeInclusive<u32>| {
+                a.start() <= b.start() && b.end() <= a.end()
+            };
+            let contained = pairs
+                .iter()
+                .filter(|(a, b)| contains(a, b) || contains(b, a))
+                .count();
+            let overlapping = pairs
+                .iter()
+                .filter(|(a, b)| a.start() <= b.end() && b.start() <= a.end())
+                .count();
+
+            prop_assert_eq!(solve_part1(&input).unwrap(), contained.into());
+            prop_assert_eq!(solve_part2(&input).unwrap(), overlapping.into());
+        }
+    }
+}
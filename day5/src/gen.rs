@@ -0,0 +1,78 @@
+//! Random stack drawings and rearrangement procedures.
+
+use crate::{Procedure, Step};
+use proptest::{collection::vec, prelude::*, sample::Index};
+
+fn stacks() -> impl Strategy<Value = Vec<Vec<char>>> {
+    vec(vec(proptest::char::range('A', 'Z'), 0..6), 2..=9)
+        .prop_filter("no crates", |stacks| stacks.iter().any(|s| !s.is_empty()))
+}
+
+/// Steps that only ever move crates that are there, picked by replaying
+/// the procedure.
+fn steps(stacks: &[Vec<char>], picks: &[(Index, Index, Index)]) -> Vec<Step> {
+    let mut heights = stacks.iter().map(|s| s.len()).collect::<Vec<_>>();
+
+    picks
+        .iter()
+        .map(|(from, to, count)| {
+            let filled = (0..heights.len())
+                .filter(|&i| heights[i] > 0)
+                .collect::<Vec<_>>();
+            let from = *from.get(&filled);
+            let others = (0..heights.len())
+                .filter(|&i| i != from)
+                .collect::<Vec<_>>();
+            let to = *to.get(&others);
+            let count = count.index(heights[from]) + 1;
+
+            heights[from] -= count;
+            heights[to] += count;
+
+            Step { count, from, to }
+        })
+        .collect()
+}
+
+pub fn procedure() -> impl Strategy<Value = Procedure> {
+    (
+        stacks(),
+        vec((any::<Index>(), any::<Index>(), any::<Index>()), 0..30),
+    )
+        .prop_map(|(stacks, picks)| Procedure {
+            steps: steps(&stacks, &picks),
+            stacks,
+        })
+}
+
+pub fn to_input(procedure: &Procedure) -> String {
+    let stacks = &procedure.stacks;
+    let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+
+    let mut input = String::new();
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|s| match s.get(level) {
+                Some(c) => format!("[{}]", c),
+                None => "   ".to_owned(),
+            })
+            .collect::<Vec<_>>();
+        input += &format!("{}\n", row.join(" "));
+    }
+
+    let numbers = (1..=stacks.len())
+        .map(|n| format!(" {} ", n))
+        .collect::<Vec<_>>();
+    input += &format!("{}\n\n", numbers.join(" "));
+
+    for Step { count, from, to } in &procedure.steps {
+        input += &format!("move {} from {} to {}\n", count, from + 1, to + 1);
+    }
+
+    input
+}
+
+pub fn input() -> impl Strategy<Value = String> {
+    procedure().prop_map(|procedure| to_input(&procedure))
+}
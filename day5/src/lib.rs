@@ -0,0 +1,175 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::{borrow_mut_elementwise, parse::parse_numbered, Answer, LineError};
+use itertools::Itertools;
+use regex::Regex;
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// The starting stacks, bottom crate first, and the steps to rearrange them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Procedure {
+    pub stacks: Vec<Vec<char>>,
+    pub steps: Vec<Step>,
+}
+
+/// Parses the stack drawing and the rearrangement steps below it. Stack
+/// numbers in the steps become 0-based indices.
+pub fn parse(input: &str) -> Result<Procedure, AnyhowError> {
+    let mut schema = input
+        .lines()
+        .take_while(|s| !s.is_empty())
+        .map(|s| {
+            let chunks = s.chars().chunks(4);
+
+            chunks
+                .into_iter()
+                .filter_map(|mut chunk| chunk.nth(1))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    schema.reverse();
+
+    let stack_count = schema.first().ok_or(anyhow!("No stack drawing"))?.len();
+    let mut stacks: Vec<Vec<char>> = vec![Vec::with_capacity(schema.len() - 1); stack_count];
+    for row in schema.into_iter().skip(1) {
+        for (i, el) in row.iter().enumerate() {
+            if *el != ' ' {
+                stacks[i].push(*el);
+            }
+        }
+    }
+
+    let regexp = Regex::new(r"^move (\d+) from (\d+) to (\d+)$")?;
+
+    let stack_number = |m: regex::Match| -> Result<usize, LineError> {
+        match m.as_str().parse::<usize>() {
+            Ok(n) if (1..=stack_count).contains(&n) => Ok(n - 1),
+            _ => Err(LineError::new(
+                m.start() + 1,
+                format!("a stack number from 1 to {}", stack_count),
+            )),
+        }
+    };
+
+    let steps = parse_numbered(
+        input
+            .lines()
+            .enumerate()
+            .skip_while(|(_, s)| !s.is_empty())
+            .skip(1),
+        |s| {
+            let cap = regexp
+                .captures(s)
+                .ok_or(LineError::new(1, "move <count> from <stack> to <stack>"))?;
+
+            Ok(Step {
+                count: cap[1].parse::<usize>().map_err(|_| {
+                    LineError::new(cap.get(1).unwrap().start() + 1, "a crate count")
+                })?,
+                from: stack_number(cap.get(2).unwrap())?,
+                to: stack_number(cap.get(3).unwrap())?,
+            })
+        },
+    )?;
+
+    Ok(Procedure { stacks, steps })
+}
+
+/// Runs the rearrangement procedure and returns the crates on top of each
+/// stack. CrateMover 9001 moves several crates at once, keeping their order.
+fn rearrange(input: &str, keep_order: bool) -> Result<String, AnyhowError> {
+    let Procedure { mut stacks, steps } = parse(input)?;
+
+    let mut mut_stacks = borrow_mut_elementwise(&mut stacks);
+    for Step { count, from, to } in steps {
+        let mut buf = Vec::with_capacity(count);
+        for _ in 0..count {
+            if let Some(c) = mut_stacks[from].pop() {
+                buf.push(c);
+            }
+        }
+        if keep_order {
+            buf.reverse();
+        }
+        mut_stacks[to].append(&mut buf);
+    }
+
+    let result = stacks
+        .into_iter()
+        .filter_map(|v| v.last().copied())
+        .join("");
+
+    Ok(result)
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(rearrange(input, false)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(rearrange(input, true)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::from("CMZ"));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::from("MCD"));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    proptest! {
+        #[test]
+        fn parses_generated_procedures(procedure in gen::procedure()) {
+            prop_assert_eq!(parse(&gen::to_input(&procedure)).unwrap(), procedure);
+        }
+
+        #[test]
+        fn cratemovers_agree_one_crate_at_a_time(procedure in gen::procedure()) {
+            let single = Procedure {
+                steps: procedure
+                    .steps
+                    .iter()
+                    .flat_map(|step| {
+                        let one = Step { count: 1, ..step.clone() };
+                        vec![one; step.count]
+                    })
+                    .collect(),
+                ..procedure.clone()
+            };
+            let (input, single) = (gen::to_input(&procedure), gen::to_input(&single));
+
+            prop_assert_eq!(solve_part1(&input).unwrap(), solve_part1(&single).unwrap());
+            prop_assert_eq!(solve_part2(&single).unwrap(), solve_part1(&single).unwrap());
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::{anyhow, Error as AnyhowError};
+use aoc_common::Answer;
+use std::collections::HashSet;
+
+/// Extracts the datastream, the first line of the input.
+pub fn parse(input: &str) -> Result<&[u8], AnyhowError> {
+    Ok(input.lines().next().ok_or(anyhow!("No input!"))?.as_bytes())
+}
+
+/// Position right after the first `window_size` distinct characters.
+fn find_marker(input: &str, window_size: usize) -> Result<usize, AnyhowError> {
+    let stream = parse(input)?;
+
+    let mut last = None;
+    for (i, window) in stream.windows(window_size).enumerate() {
+        let set = window.iter().collect::<HashSet<_>>();
+        if set.len() == window_size {
+            last = Some(i + window_size);
+            break;
+        }
+    }
+
+    last.ok_or(anyhow!("No marker found"))
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(find_marker(input, 4)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(find_marker(input, 14)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(7));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(19));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+}
@@ -1,23 +1,5 @@
-use anyhow::anyhow;
-use std::{collections::HashSet, error::Error, fs};
+use anyhow::Error as AnyhowError;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let input = fs::read_to_string("./input.txt")?;
-
-    let stream = input.lines().next().ok_or(anyhow!("No input!"))?[..].as_bytes();
-
-    let window_size = 14;
-
-    let mut last = None;
-    for (i, window) in stream.windows(window_size).enumerate() {
-        let set = window.iter().collect::<HashSet<_>>();
-        if set.len() == window_size {
-            last = Some(i + window_size);
-            break;
-        }
-    }
-
-    println!("Index: {:?}", last);
-
-    Ok(())
+fn main() -> Result<(), AnyhowError> {
+    aoc_common::day_main(6, day6::solve_part1, day6::solve_part2)
 }
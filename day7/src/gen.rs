@@ -0,0 +1,86 @@
+//! Random file system trees and the terminal sessions exploring them.
+
+use proptest::{collection::vec, prelude::*};
+
+/// A directory, with the names and sizes of its files. Every generated
+/// directory has at least one file, as empty ones can't be told from files.
+#[derive(Debug, Clone)]
+pub struct Dir {
+    pub files: Vec<(String, u32)>,
+    pub dirs: Vec<(String, Dir)>,
+}
+
+impl Dir {
+    pub fn size(&self) -> u32 {
+        self.files.iter().map(|(_, size)| size).sum::<u32>()
+            + self.dirs.iter().map(|(_, d)| d.size()).sum::<u32>()
+    }
+
+    /// Sizes of this directory and all the ones below it.
+    pub fn dir_sizes(&self) -> Vec<u32> {
+        let mut sizes = vec![self.size()];
+        for (_, d) in &self.dirs {
+            sizes.extend(d.dir_sizes());
+        }
+        sizes
+    }
+}
+
+fn files() -> impl Strategy<Value = Vec<(String, u32)>> {
+    vec(("[a-z]{1,8}(\\.[a-z]{3})?", 1..100_000u32), 1..4)
+}
+
+fn dir() -> impl Strategy<Value = Dir> {
+    let leaf = files().prop_map(|files| Dir {
+        files,
+        dirs: vec![],
+    });
+
+    leaf.prop_recursive(4, 32, 4, |inner| {
+        (files(), vec(("[a-z]{1,6}", inner), 0..4)).prop_map(|(files, dirs)| Dir {
+            files,
+            dirs: dirs
+                .into_iter()
+                .enumerate()
+                .map(|(i, (name, d))| (format!("{}{}", name, i), d))
+                .collect(),
+        })
+    })
+}
+
+/// Tree filling between 40M and 70M of the 70M disk, so that some space
+/// always has to be freed for the update. The generated directories add up
+/// to less than 40M, the rest is a filler file in the root.
+pub fn root() -> impl Strategy<Value = Dir> {
+    (dir(), 40_000_000..70_000_000u32).prop_map(|(mut root, used)| {
+        let size = root.size();
+        root.files.push(("filler.dat".to_owned(), used - size));
+        root
+    })
+}
+
+fn explore(dir: &Dir, log: &mut String) {
+    *log += "$ ls\n";
+    for (name, _) in &dir.dirs {
+        *log += &format!("dir {}\n", name);
+    }
+    for (name, size) in &dir.files {
+        *log += &format!("{} {}\n", size, name);
+    }
+
+    for (name, d) in &dir.dirs {
+        *log += &format!("$ cd {}\n", name);
+        explore(d, log);
+        *log += "$ cd ..\n";
+    }
+}
+
+pub fn to_input(root: &Dir) -> String {
+    let mut log = "$ cd /\n".to_owned();
+    explore(root, &mut log);
+    log
+}
+
+pub fn input() -> impl Strategy<Value = String> {
+    root().prop_map(|root| to_input(&root))
+}
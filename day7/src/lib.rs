@@ -0,0 +1,234 @@
+use anyhow::{anyhow, bail, Error as AnyhowError};
+use aoc_common::{debug, parse::parse_lines, Answer, LineError};
+use id_tree::{InsertBehavior, Node, NodeId, Tree};
+use std::str::FromStr;
+
+#[cfg(any(test, feature = "gen"))]
+pub mod gen;
+
+/// A line of the terminal session.
+#[derive(Debug, Clone)]
+pub enum LogLine {
+    Command {
+        name: String,
+        arg: Option<String>,
+    },
+    Dir {
+        #[allow(dead_code)]
+        name: String,
+    },
+    File {
+        name: String,
+        size: u32,
+    },
+}
+
+impl FromStr for LogLine {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split_once(' ')
+            .ok_or(LineError::new(1, "a command, a dir or a file listing"))?;
+        match parts {
+            ("$", cmd) => {
+                let splitted = cmd.split_once(' ');
+                if let Some((cmd_name, arg)) = splitted {
+                    Ok(LogLine::Command {
+                        name: cmd_name.to_owned(),
+                        arg: Some(arg.to_owned()),
+                    })
+                } else {
+                    Ok(LogLine::Command {
+                        name: cmd.to_owned(),
+                        arg: None,
+                    })
+                }
+            }
+            ("dir", name) => Ok(LogLine::Dir {
+                name: name.to_owned(),
+            }),
+            (size, name) => Ok(LogLine::File {
+                name: name.to_owned(),
+                size: size
+                    .parse()
+                    .map_err(|_| LineError::new(1, "$, dir or a file size"))?,
+            }),
+        }
+    }
+}
+
+fn find_parent<T>(tree: &Tree<T>, node_id: &NodeId) -> Result<Option<NodeId>, AnyhowError> {
+    Ok(tree.ancestor_ids(node_id)?.next().cloned())
+}
+
+fn get_data<T>(tree: &Tree<T>, node_id: &NodeId) -> Result<T, AnyhowError>
+where
+    T: Clone,
+{
+    let me = tree.get(node_id)?;
+    Ok(me.data().clone())
+}
+
+fn add_child<T>(tree: &mut Tree<T>, node_id: &NodeId, data: T) -> Result<NodeId, AnyhowError> {
+    let node = Node::new(data);
+    tree.insert(node, InsertBehavior::UnderNode(node_id))
+        .map_err(|e| e.into())
+}
+
+fn add_size_to_parent(tree: &mut Tree<(String, u32)>, node_id: &NodeId) -> Result<(), AnyhowError> {
+    let data = get_data(tree, node_id)?;
+
+    if let Some(parent_id) = find_parent(tree, node_id)? {
+        let parent = tree.get_mut(&parent_id)?;
+        let parent_data = parent.data();
+        let new_data = (parent_data.0.clone(), data.1 + parent_data.1);
+        parent.replace_data(new_data);
+    }
+
+    Ok(())
+}
+
+/// Parses the terminal session log.
+pub fn parse(input: &str) -> Result<Vec<LogLine>, AnyhowError> {
+    Ok(parse_lines(input, LogLine::from_str)?)
+}
+
+/// Replays the terminal log into a tree of `(name, size)` nodes, where each
+/// directory's size includes everything below it.
+fn build_tree(input: &str) -> Result<(Tree<(String, u32)>, NodeId), AnyhowError> {
+    let log = parse(input)?;
+
+    let mut tree: Tree<(String, u32)> = Tree::new();
+    let root_id = tree.insert(Node::new(("/".to_owned(), 0)), InsertBehavior::AsRoot)?;
+    let mut current = root_id.clone();
+
+    for line in log {
+        match line {
+            LogLine::Command {
+                name,
+                arg: Some(dir),
+            } if name == "cd" => match dir.as_str() {
+                "/" => {
+                    current = root_id.clone();
+                }
+                ".." => {
+                    current = find_parent(&tree, &current)?
+                        .ok_or(anyhow!("No parent for {:?}", current))?;
+                }
+                path => {
+                    current = add_child(&mut tree, &current, (path.to_owned(), 0))?;
+                }
+            },
+            LogLine::Command { name, arg: _ } if name == "ls" => {}
+            LogLine::Command { name, arg: _ } => bail!("Unknown command {}", name),
+            LogLine::Dir { name: _ } => {}
+            LogLine::File { name, size } => {
+                add_child(&mut tree, &current, (name.to_owned(), size))?;
+            }
+        }
+    }
+
+    for node_id in tree.traverse_post_order_ids(&root_id)? {
+        add_size_to_parent(&mut tree, &node_id)?;
+    }
+
+    if debug::is_enabled() {
+        let mut s = String::new();
+        tree.write_formatted(&mut s)?;
+        debug!("{}", s);
+    }
+
+    Ok((tree, root_id))
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    let (tree, root_id) = build_tree(input)?;
+
+    let mut total = 0;
+    for node in tree.traverse_post_order(&root_id)? {
+        let is_dir = !node.children().is_empty();
+        let size = node.data().1;
+        if is_dir && size <= 100000 {
+            total += size;
+        }
+    }
+
+    Ok(total.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    let (tree, root_id) = build_tree(input)?;
+
+    let total_space = 70000000u32;
+    let required_space = 30000000u32;
+
+    let occupied_space = tree.get(&root_id)?.data().1;
+    let free_space = total_space - occupied_space;
+    let left_to_free = required_space - free_space;
+
+    debug!(
+        "Total {}, required {}, occupied {}, free {}, left to free {}",
+        total_space, required_space, occupied_space, free_space, left_to_free
+    );
+
+    let mut min_dir = tree.get(&root_id)?.data();
+    for node in tree.traverse_post_order(&root_id)? {
+        let is_dir = !node.children().is_empty();
+        let size = node.data().1;
+        if is_dir && size >= left_to_free {
+            debug!("Big enough dir {:?}", node.data());
+            if size < min_dir.1 {
+                min_dir = node.data();
+            }
+        }
+    }
+
+    debug!("Smallest big enough dir: {:?}", min_dir);
+
+    Ok(min_dir.1.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(95437));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(24933642));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+
+    proptest! {
+        #[test]
+        fn solves_generated_sessions(root in gen::root()) {
+            let input = gen::to_input(&root);
+            let sizes = root.dir_sizes();
+
+            let small = sizes.iter().filter(|&&s| s <= 100000).sum::<u32>();
+            let to_free = root.size() - 40000000;
+            let freed = sizes.iter().filter(|&&s| s >= to_free).min().copied();
+
+            prop_assert_eq!(solve_part1(&input).unwrap(), small.into());
+            prop_assert_eq!(solve_part2(&input).unwrap(), freed.unwrap().into());
+        }
+    }
+}
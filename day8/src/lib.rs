@@ -0,0 +1,113 @@
+use anyhow::Error as AnyhowError;
+use aoc_common::{
+    debug,
+    grid::{Pos, NEIGHBORS_4},
+    Answer, Grid,
+};
+
+/// Trees seen from `pos` looking towards `dir`, up to the first one at least
+/// as tall, or to the edge of the forest.
+fn viewing_distance(forest: &Grid<u8>, pos: Pos, (dx, dy): Pos) -> u32 {
+    let height = forest[pos];
+
+    let mut seen_trees = 0;
+    let mut next = (pos.0 + dx, pos.1 + dy);
+    while let Some(&next_height) = forest.get(next) {
+        seen_trees += 1;
+
+        if next_height >= height {
+            break;
+        }
+        next = (next.0 + dx, next.1 + dy);
+    }
+
+    seen_trees
+}
+
+fn scenic_score(forest: &Grid<u8>, pos: Pos) -> u32 {
+    NEIGHBORS_4
+        .into_iter()
+        .map(|dir| viewing_distance(forest, pos, dir))
+        .product()
+}
+
+fn is_visible(forest: &Grid<u8>, pos: Pos) -> bool {
+    let height = forest[pos];
+
+    NEIGHBORS_4.into_iter().any(|(dx, dy)| {
+        let mut next = (pos.0 + dx, pos.1 + dy);
+        while let Some(&next_height) = forest.get(next) {
+            if next_height >= height {
+                return false;
+            }
+            next = (next.0 + dx, next.1 + dy);
+        }
+
+        true
+    })
+}
+
+/// Parses the tree height map.
+pub fn parse(input: &str) -> Result<Grid<u8>, AnyhowError> {
+    Grid::parse(input, "a tree height digit", |c| {
+        c.to_digit(10).map(|d| d as u8)
+    })
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    let forest = parse(input)?;
+
+    let mut visible = forest.map(|_| false);
+    for pos in forest.positions() {
+        visible[pos] = is_visible(&forest, pos);
+    }
+
+    debug!(
+        "{}",
+        visible.render(|&v| if v { '#' } else { '.' }).join("\n")
+    );
+
+    Ok(visible.iter().filter(|(_, &v)| v).count().into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    let forest = parse(input)?;
+
+    debug!("Forest of {}x{}", forest.width(), forest.height());
+
+    let max_score = forest
+        .positions()
+        .map(|pos| scenic_score(&forest, pos))
+        .max()
+        .unwrap_or(0);
+
+    Ok(max_score.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(21));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(8));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+}
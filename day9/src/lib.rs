@@ -0,0 +1,197 @@
+use anyhow::Error as AnyhowError;
+use aoc_common::{
+    debug,
+    parse::parse_lines,
+    visual::{Frame, Palette, Recorder},
+    Answer, LineError,
+};
+use std::{cmp, collections::HashSet, str::FromStr};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Move {
+    Up(u8),
+    Right(u8),
+    Down(u8),
+    Left(u8),
+}
+
+impl FromStr for Move {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir, steps) = s
+            .split_once(' ')
+            .ok_or(LineError::new(1, "a direction and a step count"))?;
+        let num: u8 = steps
+            .parse()
+            .map_err(|_| LineError::at(s, steps, "a step count"))?;
+
+        match dir {
+            "U" => Ok(Move::Up(num)),
+            "R" => Ok(Move::Right(num)),
+            "D" => Ok(Move::Down(num)),
+            "L" => Ok(Move::Left(num)),
+            _ => Err(LineError::new(1, "U, R, D or L")),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Point {
+    x: isize,
+    y: isize,
+}
+
+impl Point {
+    fn follow(&mut self, tgt: Point) {
+        let dist_x = (tgt.x - self.x).abs();
+        let dist_y = (tgt.y - self.y).abs();
+
+        let dist = cmp::max(dist_x, dist_y);
+
+        if dist < 2 {
+            return;
+        }
+
+        if dist_x > 0 {
+            if tgt.x > self.x {
+                self.x += 1;
+            } else if tgt.x < self.x {
+                self.x -= 1;
+            }
+        }
+
+        if dist_y > 0 {
+            if tgt.y > self.y {
+                self.y += 1;
+            } else if tgt.y < self.y {
+                self.y -= 1;
+            }
+        }
+    }
+}
+
+/// Parses the rope head motions.
+pub fn parse(input: &str) -> Result<Vec<Move>, AnyhowError> {
+    Ok(parse_lines(input, Move::from_str)?)
+}
+
+/// Visited cells with the rope on top, y pointing up.
+fn rope_frame(visited: &HashSet<Point>, rope: &[Point]) -> Frame {
+    let mut frame: Frame = visited.iter().map(|p| ((p.x, -p.y), '#')).collect();
+
+    for (i, knot) in rope.iter().enumerate().rev() {
+        frame.insert((knot.x, -knot.y), if i == 0 { 'H' } else { 'T' });
+    }
+
+    frame
+}
+
+/// Drags a rope of `knots` knots around for `part` and counts the positions
+/// its tail visits.
+fn simulate(input: &str, part: u8, knots: usize) -> Result<usize, AnyhowError> {
+    let moves = parse(input)?;
+
+    let mut rope = vec![Point { x: 0, y: 0 }; knots];
+
+    let mut visited: HashSet<Point> = HashSet::new();
+    visited.insert(Point { x: 0, y: 0 });
+
+    let palette = Palette::new([15, 15, 35])
+        .with('#', [90, 90, 140])
+        .with('T', [240, 200, 60])
+        .with('H', [230, 60, 60]);
+    let mut recorder = Recorder::for_part(9, part, palette)
+        .with_scale(2)
+        .with_sampling(10);
+
+    for mv in moves {
+        let mut dx = 0isize;
+        let mut dy = 0isize;
+
+        match mv {
+            Move::Up(n) => {
+                dy = n as isize;
+            }
+            Move::Down(n) => {
+                dy = -(n as isize);
+            }
+            Move::Left(n) => {
+                dx = -(n as isize);
+            }
+            Move::Right(n) => {
+                dx = n as isize;
+            }
+        }
+
+        let n = cmp::max(dx.abs(), dy.abs());
+
+        for _ in 0..n {
+            {
+                let head = &mut rope[0];
+
+                if dx.abs() > 0 {
+                    head.x += dx.signum();
+                }
+                if dy.abs() > 0 {
+                    head.y += dy.signum();
+                }
+            }
+
+            for i in 1..rope.len() {
+                let prev = rope[i - 1];
+                rope[i].follow(prev);
+            }
+
+            visited.insert(rope[rope.len() - 1]);
+        }
+
+        recorder.step(|| rope_frame(&visited, &rope));
+    }
+
+    recorder.last(|| rope_frame(&visited, &rope));
+    recorder.finish()?;
+
+    debug!(
+        "Visited:\n{}",
+        rope_frame(&visited, &[]).render('.', |&c| c).join("\n")
+    );
+
+    Ok(visited.len())
+}
+
+pub fn solve_part1(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(simulate(input, 1, 2)?.into())
+}
+
+pub fn solve_part2(input: &str) -> Result<Answer, AnyhowError> {
+    Ok(simulate(input, 2, 10)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::golden;
+
+    const EXAMPLE: &str = include_str!("../input_simple.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), Answer::Number(88));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), Answer::Number(36));
+    }
+
+    #[test]
+    fn golden_part1() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 1, solve_part1)
+    }
+
+    #[test]
+    fn golden_part2() -> Result<(), AnyhowError> {
+        golden::check(env!("CARGO_MANIFEST_DIR"), 2, solve_part2)
+    }
+}